@@ -0,0 +1,503 @@
+use rug::{Float, Integer};
+use std::fmt::Debug;
+use std::ops::{AddAssign, DivAssign, MulAssign, SubAssign};
+
+/// Default precision of the arbitrary precision backend
+pub const DEFAULT_PRECISION: u32 = 128;
+
+/// A machine integer operation exceeded the range of its type
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Overflow;
+
+impl std::fmt::Display for Overflow {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        write!(f, "integer overflow in lattice arithmetic")
+    }
+}
+
+impl std::error::Error for Overflow {}
+
+/// Common arithmetic of coefficient and floating types
+pub trait Scalar:
+    Clone
+    + Debug
+    + PartialOrd
+    + for<'a> AddAssign<&'a Self>
+    + for<'a> SubAssign<&'a Self>
+    + for<'a> MulAssign<&'a Self>
+{
+    /// Compute the inner product of two slices of equal length
+    ///
+    /// Machine integers wrap on overflow in release builds and panic in
+    /// debug builds, see `Ring::checked_dot`.
+    fn dot(a: &[Self], b: &[Self]) -> Self;
+}
+
+/**
+
+Integer coefficient type of lattice vectors
+
+A numeric backend is a pair of an integer coefficient type implementing
+`Ring` and a floating type implementing `Field`. `GSO`, `KleinSampler`,
+`GaussReduce` and `GaussSieve` are written once against these traits, so
+supporting a new number type only requires implementing them.
+
+The `i32` and `i128` backends are behind the `int-backends` feature, since
+with several machine integer backends unannotated integer literals are no
+longer inferred to be `i64`.
+
+# Examples
+
+```rust
+use rug::{Float, Integer};
+use svp::{Field, Ring};
+
+// Built-in backends: i64 with f64, Integer with Float
+let x: f64 = 3i64.to_field(53);
+let y: Float = Integer::from(3).to_field(128);
+
+assert_eq!(x, 3.0);
+assert_eq!(y.prec(), 128);
+assert_eq!(Integer::from(7).round_div(&Integer::from(2)), 4);
+```
+**/
+pub trait Ring: Scalar {
+    /// Floating type used for Gram-Schmidt and sampling
+    type Field: Field;
+
+    /// Precision of `Field` values produced by default
+    const PRECISION: u32;
+
+    /// Whether the arithmetic is exact, i.e. can never overflow
+    const EXACT: bool = false;
+
+    fn zero() -> Self;
+
+    fn one() -> Self;
+
+    fn is_zero(&self) -> bool;
+
+    fn abs(&self) -> Self;
+
+    /// Number of bits in the absolute value
+    fn bit_len(&self) -> u32;
+
+    /// Divide and round to the nearest integer
+    fn round_div(&self, d: &Self) -> Self;
+
+    /// Convert to the floating type with the given precision
+    fn to_field(&self, prec: u32) -> Self::Field;
+
+    /// Round a floating value to the nearest integer, failing if it is not
+    /// finite or out of range
+    fn from_field(x: &Self::Field) -> Result<Self, Overflow>;
+
+    /// Inner product that reports overflow instead of wrapping
+    fn checked_dot(a: &[Self], b: &[Self]) -> Result<Self, Overflow> {
+        Ok(Self::dot(a, b))
+    }
+
+    fn checked_add(&self, o: &Self) -> Result<Self, Overflow> {
+        let mut res = self.clone();
+        res += o;
+        Ok(res)
+    }
+
+    fn checked_sub(&self, o: &Self) -> Result<Self, Overflow> {
+        let mut res = self.clone();
+        res -= o;
+        Ok(res)
+    }
+
+    fn checked_mul(&self, o: &Self) -> Result<Self, Overflow> {
+        let mut res = self.clone();
+        res *= o;
+        Ok(res)
+    }
+
+    fn checked_abs(&self) -> Result<Self, Overflow> {
+        Ok(self.abs())
+    }
+
+    /// Subtract a·b in place
+    #[inline]
+    fn sub_mul(&mut self, a: &Self, b: &Self) {
+        let mut tmp = a.clone();
+        tmp *= b;
+        *self -= &tmp;
+    }
+}
+
+/// Floating type with a (possibly fixed) precision in bits
+pub trait Field: Scalar + for<'a> DivAssign<&'a Self> {
+    fn zero(prec: u32) -> Self;
+
+    fn from_f64(x: f64, prec: u32) -> Self;
+
+    fn to_f64(&self) -> f64;
+
+    fn prec(&self) -> u32;
+
+    /// Neither infinite nor NaN
+    fn is_finite(&self) -> bool;
+
+    fn sqrt(&self) -> Self;
+
+    fn ln(&self) -> Self;
+
+    fn exp(&self) -> Self;
+
+    /// The constant π with the given precision
+    fn pi(prec: u32) -> Self;
+
+    fn floor(&self) -> Self;
+
+    fn ceil(&self) -> Self;
+
+    fn round(&self) -> Self;
+}
+
+macro_rules! prim_ring_impl {
+    ($t:ty) => {
+        impl Scalar for $t {
+            #[inline]
+            fn dot(a: &[$t], b: &[$t]) -> $t {
+                let mut res = a[0] * b[0];
+                for i in 1..a.len() {
+                    res += a[i] * b[i];
+                }
+                res
+            }
+        }
+
+        impl Ring for $t {
+            type Field = f64;
+            const PRECISION: u32 = f64::MANTISSA_DIGITS;
+
+            fn zero() -> Self {
+                0
+            }
+
+            fn one() -> Self {
+                1
+            }
+
+            fn is_zero(&self) -> bool {
+                *self == 0
+            }
+
+            fn abs(&self) -> Self {
+                <$t>::abs(*self)
+            }
+
+            fn bit_len(&self) -> u32 {
+                <$t>::BITS - self.unsigned_abs().leading_zeros()
+            }
+
+            fn round_div(&self, d: &Self) -> Self {
+                // Exact, with ties rounded away from zero like `f64::round`
+                let (a, b) = (self.unsigned_abs(), d.unsigned_abs());
+                let (mut q, r) = (a / b, a % b);
+                if r >= b - r {
+                    q += 1;
+                }
+                match (*self < 0) != (*d < 0) {
+                    true => -(q as $t),
+                    false => q as $t,
+                }
+            }
+
+            fn to_field(&self, _prec: u32) -> f64 {
+                *self as f64
+            }
+
+            fn from_field(x: &f64) -> Result<Self, Overflow> {
+                // [MIN, -MIN) is exactly representable, NaN fails both checks
+                let r = x.round();
+                match r >= <$t>::MIN as f64 && r < -(<$t>::MIN as f64) {
+                    true => Ok(r as $t),
+                    false => Err(Overflow),
+                }
+            }
+
+            fn checked_dot(a: &[$t], b: &[$t]) -> Result<$t, Overflow> {
+                let mut res: $t = 0;
+                for i in 0..a.len() {
+                    res = a[i]
+                        .checked_mul(b[i])
+                        .and_then(|x| res.checked_add(x))
+                        .ok_or(Overflow)?;
+                }
+                Ok(res)
+            }
+
+            fn checked_add(&self, o: &Self) -> Result<Self, Overflow> {
+                <$t>::checked_add(*self, *o).ok_or(Overflow)
+            }
+
+            fn checked_sub(&self, o: &Self) -> Result<Self, Overflow> {
+                <$t>::checked_sub(*self, *o).ok_or(Overflow)
+            }
+
+            fn checked_mul(&self, o: &Self) -> Result<Self, Overflow> {
+                <$t>::checked_mul(*self, *o).ok_or(Overflow)
+            }
+
+            fn checked_abs(&self) -> Result<Self, Overflow> {
+                <$t>::checked_abs(*self).ok_or(Overflow)
+            }
+        }
+    };
+}
+
+impl Scalar for f64 {
+    #[inline]
+    fn dot(a: &[f64], b: &[f64]) -> f64 {
+        let mut res = a[0] * b[0];
+        for i in 1..a.len() {
+            res += a[i] * b[i];
+        }
+        res
+    }
+}
+
+impl Field for f64 {
+    fn zero(_prec: u32) -> Self {
+        0.0
+    }
+
+    fn from_f64(x: f64, _prec: u32) -> Self {
+        x
+    }
+
+    fn to_f64(&self) -> f64 {
+        *self
+    }
+
+    fn prec(&self) -> u32 {
+        f64::MANTISSA_DIGITS
+    }
+
+    fn is_finite(&self) -> bool {
+        f64::is_finite(*self)
+    }
+
+    fn sqrt(&self) -> Self {
+        f64::sqrt(*self)
+    }
+
+    fn ln(&self) -> Self {
+        f64::ln(*self)
+    }
+
+    fn exp(&self) -> Self {
+        f64::exp(*self)
+    }
+
+    fn pi(_prec: u32) -> Self {
+        std::f64::consts::PI
+    }
+
+    fn floor(&self) -> Self {
+        f64::floor(*self)
+    }
+
+    fn ceil(&self) -> Self {
+        f64::ceil(*self)
+    }
+
+    fn round(&self) -> Self {
+        f64::round(*self)
+    }
+}
+
+/* Machine integer backends */
+prim_ring_impl!(i64);
+#[cfg(feature = "int-backends")]
+prim_ring_impl!(i32);
+#[cfg(feature = "int-backends")]
+prim_ring_impl!(i128);
+
+impl Scalar for Integer {
+    #[inline]
+    fn dot(a: &[Integer], b: &[Integer]) -> Integer {
+        let mut res = Integer::from(&a[0] * &b[0]);
+        for i in 1..a.len() {
+            res += &a[i] * &b[i];
+        }
+        res
+    }
+}
+
+impl Ring for Integer {
+    type Field = Float;
+    const PRECISION: u32 = DEFAULT_PRECISION;
+    const EXACT: bool = true;
+
+    fn zero() -> Self {
+        Integer::new()
+    }
+
+    fn one() -> Self {
+        Integer::from(1)
+    }
+
+    fn is_zero(&self) -> bool {
+        *self == 0
+    }
+
+    fn abs(&self) -> Self {
+        self.clone().abs()
+    }
+
+    fn bit_len(&self) -> u32 {
+        self.significant_bits()
+    }
+
+    fn round_div(&self, d: &Self) -> Self {
+        self.clone().div_rem_round(d.clone()).0
+    }
+
+    fn to_field(&self, prec: u32) -> Float {
+        Float::with_val(prec, self)
+    }
+
+    fn from_field(x: &Float) -> Result<Self, Overflow> {
+        x.to_integer().ok_or(Overflow)
+    }
+
+    fn sub_mul(&mut self, a: &Self, b: &Self) {
+        *self -= a * b;
+    }
+}
+
+impl Scalar for Float {
+    #[inline]
+    fn dot(a: &[Float], b: &[Float]) -> Float {
+        let mut res = Float::with_val(a[0].prec(), &a[0] * &b[0]);
+        for i in 1..a.len() {
+            res += &a[i] * &b[i];
+        }
+        res
+    }
+}
+
+impl Field for Float {
+    fn zero(prec: u32) -> Self {
+        Float::new(prec)
+    }
+
+    fn from_f64(x: f64, prec: u32) -> Self {
+        Float::with_val(prec, x)
+    }
+
+    fn to_f64(&self) -> f64 {
+        Float::to_f64(self)
+    }
+
+    fn prec(&self) -> u32 {
+        Float::prec(self)
+    }
+
+    fn is_finite(&self) -> bool {
+        Float::is_finite(self)
+    }
+
+    fn sqrt(&self) -> Self {
+        self.clone().sqrt()
+    }
+
+    fn ln(&self) -> Self {
+        self.clone().ln()
+    }
+
+    fn exp(&self) -> Self {
+        self.clone().exp()
+    }
+
+    fn pi(prec: u32) -> Self {
+        Float::with_val(prec, rug::float::Constant::Pi)
+    }
+
+    fn floor(&self) -> Self {
+        self.clone().floor()
+    }
+
+    fn ceil(&self) -> Self {
+        self.clone().ceil()
+    }
+
+    fn round(&self) -> Self {
+        self.clone().round()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_prim() {
+        assert_eq!(i64::dot(&[1, 2, 3], &[4, 5, 6]), 32);
+        assert_eq!(7i64.round_div(&2), 4);
+        assert_eq!((-7i64).round_div(&2), -4);
+        assert_eq!(i64::from_field(&2.5f64.floor()), Ok(2));
+        assert_eq!(i64::from_field(&-2f64.powi(63)), Ok(i64::MIN));
+        assert_eq!(i64::from_field(&2f64.powi(63)), Err(Overflow));
+        assert_eq!(i64::from_field(&f64::NAN), Err(Overflow));
+        assert_eq!(Field::floor(&-0.5f64), -1.0);
+
+        assert_eq!(i64::checked_dot(&[3, 4], &[5, 6]), Ok(39));
+        assert_eq!(i64::checked_dot(&[1 << 32], &[1 << 32]), Err(Overflow));
+        assert_eq!(Ring::checked_abs(&i64::MIN), Err(Overflow));
+        assert_eq!((-5i64).bit_len(), 3);
+    }
+
+    #[test]
+    #[cfg(not(feature = "int-backends"))]
+    fn test_literals() {
+        // i64 is the only machine integer backend, 1 << 40 overflows an i32
+        let v = crate::Vector {
+            vec: vec![1 << 20, 0],
+            norm: None,
+        };
+        assert_eq!(&v * &v, 1 << 40);
+    }
+
+    #[test]
+    #[cfg(feature = "int-backends")]
+    fn test_int_backends() {
+        assert_eq!(i128::dot(&[1 << 40, 1], &[1 << 40, 1]), (1 << 80) + 1);
+        assert_eq!(5i32.round_div(&-2), -3);
+        assert_eq!(((1i128 << 100) + 1).round_div(&2), (1 << 99) + 1);
+        assert_eq!(Ring::checked_abs(&i32::MIN), Err(Overflow));
+        assert_eq!(i32::from_field(&3e9), Err(Overflow));
+        assert_eq!(i128::from_field(&3e9), Ok(3_000_000_000));
+    }
+
+    #[test]
+    fn test_mp() {
+        let a = [Integer::from(1), Integer::from(2), Integer::from(3)];
+        let b = [Integer::from(4), Integer::from(5), Integer::from(6)];
+        assert_eq!(<Integer as Scalar>::dot(&a, &b), 32);
+        assert_eq!(Integer::from(-7).round_div(&Integer::from(2)), -4);
+        assert_eq!(Integer::checked_dot(&a, &b), Ok(Integer::from(32)));
+
+        let x = Integer::from(5).to_field(64);
+        assert_eq!(x.prec(), 64);
+        assert_eq!(
+            Integer::from_field(&Field::sqrt(&x).round()),
+            Ok(Integer::from(2))
+        );
+        assert_eq!(
+            Integer::from_field(&Float::with_val(53, 1e300))
+                .unwrap()
+                .significant_bits(),
+            997
+        );
+        assert_eq!(
+            Integer::from_field(&Float::with_val(53, f64::INFINITY)),
+            Err(Overflow)
+        );
+    }
+}
@@ -0,0 +1,173 @@
+use super::backend::Ring;
+use super::lattice::Lattice;
+use super::normal_form::{hnf_coords, HermiteForm, NormalForm};
+use crate::{nvec, Vector};
+use rug::Integer;
+
+/**
+
+Recover the integer coefficients of lattice vectors w.r.t. the basis
+
+The coefficients z with v = Σ z_i b_i are solved for exactly by back
+substitution in the Hermite normal form H = U·B, and mapped back through U.
+Vectors outside of L(B), including those outside its span, have no
+coefficients. For generating sets the coefficients are not unique, any
+solution may be returned.
+
+# Examples
+
+```rust
+use svp::{nvec, Coordinates, Lattice, Vector};
+
+let l = Lattice {
+    basis: vec![nvec![1i64, 1, 0], nvec![1, 2, 0], nvec![0, 1, 2]],
+};
+
+let v = &nvec![3, -1, 2] * &l;
+assert_eq!(l.coordinates(&v).unwrap().vec, vec![3, -1, 2]);
+
+// (0, 0, 1) is not a lattice point
+assert!(l.coordinates(&nvec![0, 0, 1]).is_none());
+
+// Many vectors share a single Hermite normal form
+let vs = vec![v.clone(), nvec![0, 0, 1], l.basis[2].clone()];
+let zs = l.coordinates_batch(&vs);
+assert!(zs[1].is_none());
+assert_eq!(zs[2].as_ref().unwrap().vec, vec![0, 0, 1]);
+```
+**/
+pub trait Coordinates<T> {
+    /// The coefficient vector of v, or `None` if v is not in L(B) or a
+    /// coefficient exceeds the range of `T`
+    fn coordinates(&self, v: &Vector<T>) -> Option<Vector<T>>;
+
+    /// The coefficient vectors of many lattice vectors
+    fn coordinates_batch(&self, vs: &[Vector<T>]) -> Vec<Option<Vector<T>>>;
+}
+
+/// Solve z·B = v through the Hermite normal form of B
+fn solve(hnf: &HermiteForm, v: &Vector<Integer>) -> Option<Vector<Integer>> {
+    let x = hnf_coords(&hnf.h.basis, v)?;
+    let mut z = nvec![Integer::new(); hnf.u.len()];
+    for (xi, u) in x.vec.iter().zip(&hnf.u) {
+        for (zj, uj) in z.vec.iter_mut().zip(&u.vec) {
+            *zj += Integer::from(xi * uj);
+        }
+    }
+    Some(z)
+}
+
+impl<T> Coordinates<T> for Lattice<T>
+where
+    T: Ring + for<'a> TryFrom<&'a Integer>,
+    Integer: From<T>,
+{
+    fn coordinates(&self, v: &Vector<T>) -> Option<Vector<T>> {
+        self.coordinates_batch(std::slice::from_ref(v))
+            .pop()
+            .unwrap()
+    }
+
+    fn coordinates_batch(&self, vs: &[Vector<T>]) -> Vec<Option<Vector<T>>> {
+        // The empty lattice only contains the zero vector, as the empty sum
+        if self.basis.is_empty() {
+            return vs
+                .iter()
+                .map(|v| match v.vec.iter().all(T::is_zero) {
+                    true => Some(Vector {
+                        vec: vec![],
+                        norm: None,
+                    }),
+                    false => None,
+                })
+                .collect();
+        }
+        let hnf = Lattice::<Integer>::from(self).hnf();
+        vs.iter()
+            .map(|v| {
+                if v.vec.len() != self.basis[0].vec.len() {
+                    return None;
+                }
+                let z = solve(&hnf, &Vector::from(v))?;
+                Some(Vector {
+                    vec: z
+                        .vec
+                        .iter()
+                        .map(|i| T::try_from(i).ok())
+                        .collect::<Option<_>>()?,
+                    norm: None,
+                })
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::testing;
+    use crate::*;
+    use rug::Integer;
+
+    #[test]
+    fn test_prim() {
+        let mut l = testing::dim10();
+        for v in l.basis.iter_mut() {
+            v.norm = Some(&*v * &*v);
+        }
+
+        // Sieve output in terms of the original basis
+        let mut gs = gsieve![l.clone(), (l.basis.len() as f64).ln()];
+        let short_vecs = gs.sieve();
+        for (v, z) in short_vecs.iter().zip(l.coordinates_batch(&short_vecs)) {
+            assert_eq!((&z.unwrap() * &l).vec, v.vec);
+        }
+
+        // Off by one in the last coordinate
+        let mut v = &nvec![1, 0, 0, 0, 0, 0, 0, 0, 0, 0] * &l;
+        v.vec[9] += 1;
+        assert!(l.coordinates(&v).is_none());
+        assert!(l.coordinates(&nvec![1, 2, 3]).is_none());
+    }
+
+    #[test]
+    fn test_generating_set() {
+        let l: Lattice<i64> = Lattice {
+            basis: vec![
+                nvec![2, 0, 1, 4],
+                nvec![1, 1, 0, 2],
+                nvec![3, 1, 1, 6],
+                nvec![0, 0, 0, 0],
+            ],
+        };
+        let v = &nvec![2, -1, 1, 7] * &l;
+        let z = l.coordinates(&v).unwrap();
+        assert_eq!((&z * &l).vec, v.vec);
+
+        // Outside of the span
+        assert!(l.coordinates(&nvec![0, 0, 0, 1]).is_none());
+
+        // v fits in an i64, but its coefficient 2·i64::MAX does not
+        let l: Lattice<i64> = Lattice {
+            basis: vec![nvec![1, 0], nvec![1, 1]],
+        };
+        assert!(l.coordinates(&nvec![i64::MAX, -i64::MAX]).is_none());
+        assert_eq!(l.coordinates(&nvec![5, -2]).unwrap().vec, vec![7, -2]);
+
+        let l: Lattice<i64> = Lattice { basis: vec![] };
+        assert!(l.coordinates(&nvec![0, 0]).unwrap().vec.is_empty());
+        assert!(l.coordinates(&nvec![0, 1]).is_none());
+    }
+
+    #[test]
+    fn test_mp() {
+        let big: Integer = Integer::from(1) << 200;
+        let l = Lattice {
+            basis: vec![
+                nvec![big.clone() + 1, big.clone()],
+                nvec![big.clone(), big.clone() - 1],
+            ],
+        };
+        let z = nvec![big.clone(), Integer::from(-3)];
+        assert_eq!(l.coordinates(&(&z * &l)).unwrap().vec, z.vec);
+    }
+}
@@ -0,0 +1,235 @@
+use super::lattice::Lattice;
+use crate::Vector;
+use rug::{Integer, Rational};
+
+/**
+
+The dual basis of a lattice, scaled to an integer basis
+
+The dual basis D = (B·Bᵀ)⁻¹·B spans the dual lattice
+L* = { x ∈ span(L) : ⟨x, v⟩ ∈ Z for all v ∈ L } and satisfies
+⟨d_i, b_j⟩ = δ_{i,j}, also for bases of lattices that are not full rank.
+Since D is rational in general, it is returned as the integer basis s·D
+for the least such scale s > 0.
+
+The reversed dual lists the dual vectors in the opposite order, so its
+Gram-Schmidt vectors are those of B in reverse order, inverted:
+‖d*_i‖ = 1 / ‖b*_{n-1-i}‖. Reducing the reversed dual is what dual
+variants of BKZ rely on.
+
+# Examples
+
+```rust
+use rug::Integer;
+use svp::{nvec, Dual, Lattice, Vector};
+
+let l = Lattice {
+    basis: vec![nvec![2i64, 0], nvec![1, 3]],
+};
+
+let dual = l.dual();
+assert_eq!(dual.scale, 6);
+assert_eq!(dual.basis.basis[0].vec, vec![3, -1]);
+assert_eq!(dual.basis.basis[1].vec, vec![0, 2]);
+
+// ⟨d_i, b_j⟩ = s·δ_{i,j}
+let b = Lattice::<Integer>::from(&l);
+assert_eq!(&dual.basis.basis[0] * &b.basis[0], 6);
+assert_eq!(&dual.basis.basis[0] * &b.basis[1], 0);
+
+// A rank 1 lattice in Z^3
+let l = Lattice {
+    basis: vec![nvec![1i64, 1, 0]],
+};
+let dual = l.dual();
+assert_eq!(dual.scale, 2);
+assert_eq!(dual.basis.basis[0].vec, vec![1, 1, 0]);
+```
+**/
+
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct DualBasis {
+    pub basis: Lattice<Integer>, // s·D
+    #[cfg_attr(feature = "serde", serde(with = "crate::algebra::serialize::scalar"))]
+    pub scale: Integer, // s
+}
+
+/// Compute the dual basis of a lattice
+pub trait Dual {
+    /// Panics if the basis vectors are linearly dependent
+    fn dual(&self) -> DualBasis;
+
+    /// The dual basis in reverse order
+    fn reversed_dual(&self) -> DualBasis;
+}
+
+/// Solve (B·Bᵀ)·D = B by Gauss-Jordan elimination over the rationals
+fn dual_rational(b: &[Vector<Integer>]) -> Vec<Vec<Rational>> {
+    let n = b.len();
+    let mut g: Vec<Vec<Rational>> = (0..n)
+        .map(|i| (0..n).map(|j| Rational::from(&b[i] * &b[j])).collect())
+        .collect();
+    let mut d: Vec<Vec<Rational>> = b
+        .iter()
+        .map(|i| i.vec.iter().map(Rational::from).collect())
+        .collect();
+
+    for k in 0..n {
+        let pivot = (k..n)
+            .find(|i| g[*i][k] != 0)
+            .expect("basis vectors are linearly dependent");
+        g.swap(k, pivot);
+        d.swap(k, pivot);
+
+        let inv = Rational::from(g[k][k].recip_ref());
+        for x in g[k].iter_mut().chain(d[k].iter_mut()) {
+            *x *= &inv;
+        }
+        for i in 0..n {
+            if i == k || g[i][k] == 0 {
+                continue;
+            }
+            let f = g[i][k].clone();
+            for j in 0..n {
+                let tmp = Rational::from(&f * &g[k][j]);
+                g[i][j] -= tmp;
+            }
+            for j in 0..d[i].len() {
+                let tmp = Rational::from(&f * &d[k][j]);
+                d[i][j] -= tmp;
+            }
+        }
+    }
+    d
+}
+
+/// Scale a rational matrix to the least integer multiple
+fn scale(d: Vec<Vec<Rational>>) -> DualBasis {
+    let mut s = Integer::from(1);
+    for x in d.iter().flatten() {
+        s.lcm_mut(x.denom());
+    }
+    let basis = d
+        .into_iter()
+        .map(|row| {
+            let vec: Vec<Integer> = row
+                .into_iter()
+                .map(|x| {
+                    let (num, den) = x.into_numer_denom();
+                    num * Integer::from(&s / &den)
+                })
+                .collect();
+            let mut v = Vector { vec, norm: None };
+            v.norm = Some(&v * &v);
+            v
+        })
+        .collect();
+    DualBasis {
+        basis: Lattice { basis },
+        scale: s,
+    }
+}
+
+impl<T> Dual for Lattice<T>
+where
+    T: Clone,
+    Integer: From<T>,
+{
+    fn dual(&self) -> DualBasis {
+        let b = Lattice::<Integer>::from(self);
+        scale(dual_rational(&b.basis))
+    }
+
+    fn reversed_dual(&self) -> DualBasis {
+        let mut d = self.dual();
+        d.basis.basis.reverse();
+        d
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::testing;
+    use crate::*;
+    use rug::ops::Pow;
+    use rug::{Integer, Rational};
+
+    #[test]
+    fn test_prim() {
+        let l = testing::dim10();
+        let b = Lattice::<Integer>::from(&l);
+        let d = l.dual();
+        for (i, di) in d.basis.basis.iter().enumerate() {
+            for (j, bj) in b.basis.iter().enumerate() {
+                let expected = match i == j {
+                    true => d.scale.clone(),
+                    false => Integer::new(),
+                };
+                assert_eq!(di * bj, expected);
+            }
+        }
+
+        // vol(L*) = 1 / vol(L)
+        let s2 = Integer::from(d.scale.square_ref());
+        let det = d.basis.gram_det() * l.gram_det();
+        assert_eq!(det, s2.clone().pow(10u32));
+
+        // ‖d*_i‖² · ‖b*_{n-1-i}‖² = s² for the reversed dual
+        let r = l.reversed_dual();
+        let (gd, gb) = (r.basis.exact_gso(), b.exact_gso());
+        let norm = |d: &[Integer], i: usize| Rational::from((d[i + 1].clone(), d[i].clone()));
+        for i in 0..10 {
+            assert_eq!(norm(&gd.d, i) * norm(&gb.d, 9 - i), Rational::from(&s2));
+        }
+    }
+
+    #[test]
+    fn test_rank_deficient() {
+        // A rank 2 lattice in Z^4
+        let l: Lattice<i64> = Lattice {
+            basis: vec![nvec![1, 2, 0, 1], nvec![0, 1, 3, 1]],
+        };
+        let d = l.dual();
+        let b = Lattice::<Integer>::from(&l);
+        assert_eq!(&d.basis.basis[0] * &b.basis[0], d.scale);
+        assert_eq!(&d.basis.basis[0] * &b.basis[1], 0);
+        assert_eq!(&d.basis.basis[1] * &b.basis[1], d.scale);
+
+        // The dual lies in the span of L
+        let mut span = b.clone();
+        span.basis.extend(d.basis.basis.iter().cloned());
+        assert_eq!(span.rank(), 2);
+
+        // Z^n is self-dual
+        let z: Lattice<i64> = Lattice {
+            basis: vec![nvec![1, 0, 0], nvec![0, 1, 0], nvec![0, 0, 1]],
+        };
+        assert_eq!(z.dual().scale, 1);
+        assert_eq!(z.reversed_dual().basis.basis[0].vec, vec![0, 0, 1]);
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_dependent() {
+        let l: Lattice<i64> = Lattice {
+            basis: vec![nvec![1, 2, 3], nvec![2, 4, 6]],
+        };
+        l.dual();
+    }
+
+    #[test]
+    fn test_mp() {
+        let big: Integer = Integer::from(1) << 200;
+        let l = Lattice {
+            basis: vec![
+                nvec![big.clone(), Integer::new()],
+                nvec![Integer::from(1), Integer::from(3)],
+            ],
+        };
+        let d = l.dual();
+        assert_eq!(d.scale, Integer::from(&big * 3));
+        assert_eq!(&d.basis.basis[1] * &l.basis[1], d.scale);
+        assert_eq!(&d.basis.basis[1] * &l.basis[0], 0);
+    }
+}
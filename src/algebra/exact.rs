@@ -0,0 +1,189 @@
+use super::gram::GramLattice;
+use crate::{Lattice, Vector};
+use rug::{Integer, Rational};
+
+/**
+
+Exact Gram-Schmidt Orthogonalization using only integer arithmetic
+
+Implements the integral Gram-Schmidt process of de Weger, as given in
+Algorithm 2.6.7 of Cohen's *A Course in Computational Algebraic Number Theory*.
+With `d_0 = 1` and `d_{i+1} = d_i * ‖b*_i‖²`, every `d_i` and every
+`λ_{i,j} = d_{j+1} * μ_{i,j}` is an integer, so the orthogonalization is
+computed without rounding regardless of the size of the entries.
+
+# Examples
+
+```rust
+use rug::{Integer, Rational};
+use svp::{nvec, ExactGSO, Lattice, Vector};
+
+let l = Lattice {
+    basis: vec![nvec![1, 1, 0], nvec![1, 2, 0], nvec![0, 1, 2]],
+};
+
+let gs = l.exact_gso();
+
+// ‖b*_1‖² = 1/2 and μ_{1,0} = 3/2
+assert_eq!(gs.norm(1), Rational::from((1, 2)));
+assert_eq!(gs.mu(1, 0), Rational::from((3, 2)));
+
+// d_n is the squared volume of the lattice
+assert_eq!(gs.d[3], 4);
+```
+**/
+
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct IntegralGSO {
+    #[cfg_attr(feature = "serde", serde(with = "crate::algebra::serialize::entries"))]
+    pub d: Vec<Integer>, // d[i] = Π_{j < i} ‖b*_j‖²
+    #[cfg_attr(feature = "serde", serde(with = "crate::algebra::serialize::rows"))]
+    pub lambda: Vec<Vec<Integer>>, // lambda[i][j] = d[j + 1] * μ_{i,j} for j < i
+}
+
+/// Compute the exact Gram-Schmidt Orthogonalization of B
+pub trait ExactGSO {
+    /// Panics if the basis vectors are linearly dependent, or if a Gram
+    /// matrix is not positive definite
+    fn exact_gso(&self) -> IntegralGSO;
+}
+
+impl<T> ExactGSO for Lattice<T>
+where
+    T: Clone,
+    Integer: From<T>,
+{
+    fn exact_gso(&self) -> IntegralGSO {
+        let b: Vec<Vector<Integer>> = self.basis.iter().map(Vector::from).collect();
+        integral_gso(b.len(), |k, j| &b[k] * &b[j]).expect("basis vectors are linearly dependent")
+    }
+}
+
+impl<T> ExactGSO for GramLattice<T>
+where
+    T: Clone,
+    Integer: From<T>,
+{
+    fn exact_gso(&self) -> IntegralGSO {
+        let g: Vec<Vector<Integer>> = self.gram.iter().map(Vector::from).collect();
+        integral_gso(g.len(), |k, j| g[k].vec[j].clone())
+            .expect("Gram matrix is not positive definite")
+    }
+}
+
+/// Integral GSO from the inner products `ip(k, j)`, or `None` unless
+/// every leading minor `d_i` of the Gram matrix is positive
+pub(super) fn integral_gso<F>(n: usize, ip: F) -> Option<IntegralGSO>
+where
+    F: Fn(usize, usize) -> Integer,
+{
+    let mut d = vec![Integer::from(1)];
+    let mut lambda: Vec<Vec<Integer>> = vec![];
+
+    for k in 0..n {
+        let mut row = vec![];
+        for j in 0..=k {
+            let mut u = ip(k, j);
+            for i in 0..j {
+                let l_ji = match j == k {
+                    true => &row[i],
+                    false => &lambda[j][i],
+                };
+                u = (Integer::from(&d[i + 1] * &u) - Integer::from(&row[i] * l_ji))
+                    .div_exact(&d[i]);
+            }
+            match j < k {
+                true => row.push(u),
+                false => {
+                    if u <= 0 {
+                        return None;
+                    }
+                    d.push(u);
+                }
+            }
+        }
+        lambda.push(row);
+    }
+
+    Some(IntegralGSO { d, lambda })
+}
+
+impl IntegralGSO {
+    /// The Gram-Schmidt coefficient μ_{i,j} for j < i
+    pub fn mu(&self, i: usize, j: usize) -> Rational {
+        assert!(j < i);
+        Rational::from((&self.lambda[i][j], &self.d[j + 1]))
+    }
+
+    /// The squared norm ‖b*_i‖²
+    pub fn norm(&self, i: usize) -> Rational {
+        Rational::from((&self.d[i + 1], &self.d[i]))
+    }
+
+    /// The squared norms of all Gram-Schmidt vectors
+    pub fn norms(&self) -> Vec<Rational> {
+        (0..self.d.len() - 1).map(|i| self.norm(i)).collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::*;
+    use rug::{Integer, Rational};
+
+    #[test]
+    fn test_prim() {
+        let l = Lattice {
+            basis: vec![nvec![1, 1, 0], nvec![1, 2, 0], nvec![0, 1, 2]],
+        };
+        let gs = l.exact_gso();
+        let norms = gs.norms();
+        assert_eq!(
+            norms,
+            vec![Rational::from(2), Rational::from((1, 2)), Rational::from(4)]
+        );
+        assert_eq!(gs.mu(1, 0), Rational::from((3, 2)));
+        assert_eq!(gs.mu(2, 0), Rational::from((1, 2)));
+        assert_eq!(gs.mu(2, 1), Rational::from(1));
+    }
+
+    #[test]
+    fn test_mp() {
+        let l = Lattice {
+            basis: vec![
+                nvec![Integer::from(1), Integer::from(-1), Integer::from(1)],
+                nvec![Integer::from(1), Integer::new(), Integer::from(1)],
+                nvec![Integer::from(1), Integer::from(1), Integer::from(2)],
+            ],
+        };
+
+        let gs = l.exact_gso();
+        let approx = l.gso();
+        for (i, g) in approx.iter().enumerate() {
+            let diff = g.norm.clone().unwrap() - gs.norm(i);
+            assert!(diff.abs() < 1e-30);
+        }
+
+        // Entries far beyond the range of a 128-bit float mantissa
+        let big: Integer = Integer::from(1) << 300;
+        let l = Lattice {
+            basis: vec![
+                nvec![big.clone() + 1, Integer::from(1)],
+                nvec![big.clone(), Integer::from(1)],
+            ],
+        };
+        let gs = l.exact_gso();
+        assert_eq!(gs.d[2], 1);
+        assert_eq!(gs.norm(1) * gs.norm(0), 1);
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_dependent() {
+        let l = Lattice {
+            basis: vec![nvec![1, 2], nvec![2, 4]],
+        };
+        l.exact_gso();
+    }
+}
@@ -0,0 +1,289 @@
+use super::backend::{Field, Overflow, Ring};
+use super::exact::integral_gso;
+use super::lattice::{estimate_precision, gram_rank, gso_adaptive, Lattice, GSO};
+use crate::{nvec, Vector};
+use rug::Integer;
+
+/**
+
+A lattice given by the positive definite Gram matrix G = B·Bᵀ of some basis B
+
+Lattice vectors are represented by their integer coefficient vectors z,
+with inner product zᵀ·G·z' and squared norm zᵀ·G·z. Quadratic forms and
+projected lattices, which often only come with a Gram matrix, can be
+reduced, sampled, enumerated and sieved without an explicit basis.
+
+# Examples
+
+```rust
+use svp::{nvec, Enumerate, GramLattice, Lattice, Vector, GSO};
+
+// The hexagonal lattice A2 as a binary quadratic form
+let g = GramLattice::new(vec![nvec![2i64, -1], nvec![-1, 2]]);
+
+// Inner products are taken with respect to G
+let z = nvec![1i64, 1];
+assert_eq!(g.norm(&z), 2);
+
+// The Gram-Schmidt vectors are expressed in coefficient space
+let gs = g.gso();
+assert_eq!(gs[1].vec, vec![0.5, 1.0]);
+assert_eq!(gs[1].norm, Some(1.5));
+
+// A shortest coefficient vector
+assert_eq!(g.shortest().norm, Some(2));
+
+// The Gram matrix of an explicit basis
+let l = Lattice {
+    basis: vec![nvec![1i64, 1, 0], nvec![1, 2, 0], nvec![0, 1, 2]],
+};
+assert_eq!(l.gram().gram[1].vec, vec![3, 5, 2]);
+```
+**/
+
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(bound = "T: crate::SerdeScalar"))]
+pub struct GramLattice<T> {
+    pub gram: Vec<Vector<T>>, // gram[i].vec[j] = <b_i, b_j>
+}
+
+impl<T> GramLattice<T>
+where
+    T: Ring,
+    Integer: From<T>,
+{
+    /// Panics unless `gram` is symmetric and positive definite
+    pub fn new(gram: Vec<Vector<T>>) -> Self {
+        let n = gram.len();
+        for i in 0..n {
+            assert_eq!(gram[i].vec.len(), n, "Gram matrix is not square");
+            for j in 0..i {
+                assert!(
+                    gram[i].vec[j] == gram[j].vec[i],
+                    "Gram matrix is not symmetric"
+                );
+            }
+        }
+
+        let g: Vec<Vector<Integer>> = gram.iter().map(Vector::from).collect();
+        assert!(
+            integral_gso(n, |k, j| g[k].vec[j].clone()).is_some(),
+            "Gram matrix is not positive definite"
+        );
+
+        Self { gram }
+    }
+}
+
+impl<T: Ring> GramLattice<T> {
+    /// The rank of the lattice
+    pub fn dim(&self) -> usize {
+        self.gram.len()
+    }
+
+    /// Inner product uᵀ·G·v of two coefficient vectors
+    pub fn inner(&self, u: &Vector<T>, v: &Vector<T>) -> T {
+        let gv: Vec<T> = self.gram.iter().map(|g| T::dot(&g.vec, &v.vec)).collect();
+        T::dot(&u.vec, &gv)
+    }
+
+    /// Squared norm zᵀ·G·z of a coefficient vector
+    pub fn norm(&self, z: &Vector<T>) -> T {
+        self.inner(z, z)
+    }
+
+    /// Inner product uᵀ·G·v, failing instead of overflowing
+    pub fn checked_inner(&self, u: &Vector<T>, v: &Vector<T>) -> Result<T, Overflow> {
+        let gv = self
+            .gram
+            .iter()
+            .map(|g| T::checked_dot(&g.vec, &v.vec))
+            .collect::<Result<Vec<T>, _>>()?;
+        T::checked_dot(&u.vec, &gv)
+    }
+
+    /// The i-th unit coefficient vector, i.e. the basis vector b_i
+    pub fn unit(&self, i: usize) -> Vector<T> {
+        let mut e = nvec![T::zero(); self.dim()];
+        e.vec[i] = T::one();
+        e.norm = Some(self.gram[i].vec[i].clone());
+        e
+    }
+
+    /// Heuristic precision for the GSO of this Gram matrix
+    ///
+    /// The entries are already inner products, and the rounding error may
+    /// grow by about one bit per basis vector.
+    pub fn estimate_precision(&self) -> u32 {
+        estimate_precision(&self.gram, 1)
+    }
+
+    /// Gram-Schmidt coefficients μ_{i,j} (j < i) and squared norms ‖b*_i‖²
+    /// with `prec` bits of precision, computed from G alone
+    pub fn gso_coef(&self, prec: u32) -> (Vec<Vec<T::Field>>, Vec<T::Field>) {
+        let mut mu: Vec<Vec<T::Field>> = vec![];
+        let mut r: Vec<T::Field> = vec![];
+
+        for i in 0..self.dim() {
+            // r_{i,j} = <b_i, b*_j> = G_{i,j} - Σ_{k < j} μ_{j,k} r_{i,k}
+            let mut row: Vec<T::Field> = vec![];
+            for j in 0..i {
+                let mut rij = self.gram[i].vec[j].to_field(prec);
+                for k in 0..j {
+                    let mut tmp = mu[j][k].clone();
+                    tmp *= &row[k];
+                    rij -= &tmp;
+                }
+                row.push(rij);
+            }
+
+            // ‖b*_i‖² = G_{i,i} - Σ_{j < i} μ_{i,j} r_{i,j}
+            let mut ri = self.gram[i].vec[i].to_field(prec);
+            for (j, x) in row.iter_mut().enumerate() {
+                // b*_j vanishes for linearly dependent b_j
+                if r[j] == T::Field::zero(prec) {
+                    *x = T::Field::zero(prec);
+                    continue;
+                }
+                let mut tmp = x.clone();
+                *x /= &r[j];
+                tmp *= &*x;
+                ri -= &tmp;
+            }
+            r.push(ri);
+            mu.push(row);
+        }
+
+        (mu, r)
+    }
+}
+
+/// Orthogonalize in coefficient space: b*_i is given by its coefficients
+/// with respect to B, and its norm is taken with respect to G
+impl<T: Ring> GSO<T::Field> for GramLattice<T> {
+    fn gso(&self) -> Vec<Vector<T::Field>> {
+        self.gso_prec(T::PRECISION)
+    }
+
+    fn gso_prec(&self, prec: u32) -> Vec<Vector<T::Field>> {
+        let n = self.dim();
+        let (mu, r) = self.gso_coef(prec);
+        let mut gs: Vec<Vector<T::Field>> = vec![];
+
+        // b*_i = b_i - Σ_{j < i} μ_{i,j} b*_j
+        for i in 0..n {
+            let mut g = nvec![T::Field::zero(prec); n];
+            g.vec[i] = T::Field::from_f64(1.0, prec);
+            for j in 0..i {
+                for k in 0..=j {
+                    let mut tmp = mu[i][j].clone();
+                    tmp *= &gs[j].vec[k];
+                    g.vec[k] -= &tmp;
+                }
+            }
+            g.norm = Some(r[i].clone());
+            gs.push(g);
+        }
+
+        gs
+    }
+
+    fn gso_adaptive(&self) -> Vec<Vector<T::Field>> {
+        let rank = || gram_rank(self.gram.iter().map(|i| i.vec.clone()).collect());
+        gso_adaptive(self, self.estimate_precision().max(T::PRECISION), rank)
+    }
+}
+
+impl<T: Ring> Lattice<T> {
+    /// The Gram matrix B·Bᵀ of the basis
+    pub fn gram(&self) -> GramLattice<T> {
+        GramLattice {
+            gram: self
+                .basis
+                .iter()
+                .map(|i| Vector {
+                    vec: self.basis.iter().map(|j| i * j).collect(),
+                    norm: None,
+                })
+                .collect(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::*;
+    use rug::{Integer, Rational};
+
+    #[test]
+    fn test_prim() {
+        let l = Lattice {
+            basis: vec![nvec![1i64, 1, 0], nvec![1, 2, 0], nvec![0, 1, 2]],
+        };
+        let g = l.gram();
+
+        // Same Gram-Schmidt norms as the explicit basis
+        let gs = l.gso();
+        let ggs = g.gso();
+        for i in 0..gs.len() {
+            assert!((gs[i].norm.unwrap() - ggs[i].norm.unwrap()).abs() < 1e-12);
+        }
+
+        // b*_i expressed in coefficient space maps back to the explicit b*_i
+        for i in 0..gs.len() {
+            for k in 0..3 {
+                let x: f64 = (0..3)
+                    .map(|j| ggs[i].vec[j] * l.basis[j].vec[k] as f64)
+                    .sum();
+                assert!((x - gs[i].vec[k]).abs() < 1e-12);
+            }
+        }
+
+        let z = nvec![2i64, -1, 3];
+        let v = &z * &l;
+        assert_eq!(g.norm(&z), v.norm.unwrap());
+        assert_eq!(g.checked_inner(&z, &g.unit(1)), Ok(&v * &l.basis[1]));
+    }
+
+    #[test]
+    fn test_mp() {
+        let g = GramLattice::new(vec![
+            nvec![Integer::from(4), Integer::from(2), Integer::from(2)],
+            nvec![Integer::from(2), Integer::from(4), Integer::from(2)],
+            nvec![Integer::from(2), Integer::from(2), Integer::from(4)],
+        ]);
+
+        let exact = g.exact_gso();
+        assert_eq!(exact.norms()[2], Rational::from(8) / 3);
+
+        let gs = g.gso_prec(200);
+        assert_eq!(gs[2].vec[0].prec(), 200);
+        for (i, x) in gs.iter().enumerate() {
+            let diff = x.norm.clone().unwrap() - exact.norm(i);
+            assert!(diff.abs() < 1e-50);
+        }
+        assert_eq!(
+            g.norm(&nvec![Integer::from(1), Integer::from(-1), Integer::new()]),
+            4
+        );
+    }
+
+    #[test]
+    fn test_semidefinite() {
+        // The Gram matrix of a dependent generating set
+        let l = Lattice {
+            basis: vec![nvec![1i64, 2, 3], nvec![2, 4, 6], nvec![1, 0, 0]],
+        };
+        let (mu, r) = l.gram().gso_coef(53);
+        assert_eq!(r[1], 0.0);
+        assert_eq!(mu[2][1], 0.0);
+        assert!((r[2] - 13.0 / 14.0).abs() < 1e-12);
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_indefinite() {
+        GramLattice::new(vec![nvec![1i64, 2], nvec![2, 1]]);
+    }
+}
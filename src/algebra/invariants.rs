@@ -0,0 +1,349 @@
+use super::backend::{Ring, DEFAULT_PRECISION};
+use super::gram::GramLattice;
+use super::lattice::Lattice;
+use crate::Vector;
+use rug::float::Constant;
+use rug::{Float, Integer};
+
+/**
+
+Invariants of a lattice and quality measures of its basis
+
+Volumes are derived from the exact determinant of the Gram matrix, and
+`gaussian_heuristic` estimates λ₁(L) by the radius of a ball of volume
+vol(L), i.e. `Γ(n/2 + 1)^(1/n) · vol(L)^(1/n) / √π`.
+
+# Examples
+
+```rust
+use svp::{nvec, Enumerate, Invariants, Lattice, Vector};
+
+let l = Lattice {
+    basis: vec![nvec![1i64, 1, 0], nvec![1, 2, 0], nvec![0, 1, 2]],
+};
+
+assert_eq!(l.rank(), 3);
+
+// vol(L)² = det(B·Bᵀ)
+assert_eq!(l.gram_det(), 4);
+assert_eq!(l.volume(64), 2);
+
+// ‖b_0‖ / vol(L)^(1/n) and its n-th root
+let hf = l.hermite_factor();
+assert!((hf.powi(3) - 2f64.powf(1.5) / 2.0).abs() < 1e-9);
+assert!((l.root_hermite_factor().powi(3) - hf).abs() < 1e-9);
+
+// How far a shortest vector is from the gaussian heuristic
+let v = l.shortest();
+let ratio = l.gh_ratio(&v);
+```
+
+Linearly dependent generating sets have a rank below their length, and
+the volume and the gaussian heuristic are those of the lattice they
+generate. A `GramLattice` has no basis to recover them from, so it must
+be nonsingular.
+
+```rust
+use svp::{nvec, Invariants, Lattice, Vector};
+
+let l = Lattice {
+    basis: vec![nvec![1i64, 2, 3], nvec![2, 4, 6], nvec![0, 1, 1]],
+};
+assert_eq!(l.rank(), 2);
+assert_eq!(l.gram_det(), 0);
+
+// L is spanned by (1, 2, 3) and (0, 1, 1), with vol(L)² = 14·2 - 5²
+let vol = l.volume(64).to_f64();
+assert!((vol * vol - 3.0).abs() < 1e-12);
+assert!(l.gaussian_heuristic(64) > 0);
+```
+**/
+pub trait Invariants<T> {
+    /// The dimension of the space spanned by the basis vectors
+    fn rank(&self) -> usize;
+
+    /// The exact determinant of the Gram matrix, i.e. vol(L)² for a basis
+    fn gram_det(&self) -> Integer;
+
+    /// The volume vol(L) with `prec` bits of precision
+    fn volume(&self, prec: u32) -> Float;
+
+    /// The gaussian heuristic estimate of λ₁(L) with `prec` bits of precision
+    fn gaussian_heuristic(&self, prec: u32) -> Float;
+
+    /// The Hermite factor ‖b_0‖ / vol(L)^(1/n) of the basis, NaN if it is empty
+    fn hermite_factor(&self) -> f64;
+
+    /// The root Hermite factor (‖b_0‖ / vol(L)^(1/n))^(1/n) of the basis
+    fn root_hermite_factor(&self) -> f64;
+
+    /// The ratio ‖v‖ / GH(L), at most about 1 for a shortest vector
+    fn gh_ratio(&self, v: &Vector<T>) -> f64;
+}
+
+/// Rank and determinant by fraction-free (Bareiss) elimination
+fn bareiss(mut m: Vec<Vec<Integer>>) -> (usize, Integer) {
+    let n = m.len();
+    let mut prev = Integer::from(1);
+    let mut sign = 1;
+    let mut rank = 0;
+
+    for k in 0..m.first().map_or(0, |r| r.len()) {
+        let pivot = match (rank..n).find(|i| m[*i][k] != 0) {
+            Some(p) => p,
+            None => continue,
+        };
+        if pivot != rank {
+            m.swap(pivot, rank);
+            sign = -sign;
+        }
+        for i in rank + 1..n {
+            for j in k + 1..m[i].len() {
+                let x =
+                    Integer::from(&m[rank][k] * &m[i][j]) - Integer::from(&m[i][k] * &m[rank][j]);
+                m[i][j] = x.div_exact(&prev);
+            }
+            m[i][k] = Integer::new();
+        }
+        prev = m[rank][k].clone();
+        rank += 1;
+    }
+
+    match rank == n {
+        true => (rank, prev * sign),
+        false => (rank, Integer::new()),
+    }
+}
+
+/// The Gram determinant of a nonsingular Gram matrix
+fn nonsingular_det<T>(g: &GramLattice<T>) -> Integer
+where
+    T: Clone,
+    Integer: From<T>,
+{
+    let (rank, det) = bareiss(gram_integer(g));
+    assert_eq!(
+        rank,
+        g.gram.len(),
+        "the basis vectors are linearly dependent"
+    );
+    det
+}
+
+/// ln(vol(L)^(1/n)) for a Gram determinant d of an n-dimensional lattice
+fn log_root_volume(d: &Integer, n: usize, prec: u32) -> Float {
+    Float::with_val(prec, d).ln() / (2 * n) as u32
+}
+
+impl<T> Invariants<T> for GramLattice<T>
+where
+    T: Ring,
+    Integer: From<T>,
+{
+    fn rank(&self) -> usize {
+        bareiss(gram_integer(self)).0
+    }
+
+    fn gram_det(&self) -> Integer {
+        bareiss(gram_integer(self)).1
+    }
+
+    fn volume(&self, prec: u32) -> Float {
+        Float::with_val(prec, nonsingular_det(self)).sqrt()
+    }
+
+    fn gaussian_heuristic(&self, prec: u32) -> Float {
+        let n = self.dim();
+        let lg = Float::with_val(prec, n as f64 / 2.0 + 1.0).ln_gamma() / n as u32;
+        let pi = Float::with_val(prec, Constant::Pi);
+        (lg + log_root_volume(&nonsingular_det(self), n, prec)).exp() / pi.sqrt()
+    }
+
+    fn hermite_factor(&self) -> f64 {
+        let n = self.dim();
+        let b0 = match self.gram.first() {
+            Some(g0) => Float::with_val(DEFAULT_PRECISION, Integer::from(g0.vec[0].clone())),
+            None => return f64::NAN,
+        };
+        let lr = log_root_volume(&nonsingular_det(self), n, DEFAULT_PRECISION);
+        (b0.ln() / 2u32 - lr).exp().to_f64()
+    }
+
+    fn root_hermite_factor(&self) -> f64 {
+        self.hermite_factor().powf(1.0 / self.dim() as f64)
+    }
+
+    fn gh_ratio(&self, v: &Vector<T>) -> f64 {
+        let norm = match &v.norm {
+            Some(norm) => norm.clone(),
+            None => self.norm(v),
+        };
+        let norm = Float::with_val(DEFAULT_PRECISION, Integer::from(norm)).sqrt();
+        (norm / self.gaussian_heuristic(DEFAULT_PRECISION)).to_f64()
+    }
+}
+
+/// The Gram matrix with arbitrary precision entries
+fn gram_integer<T>(g: &GramLattice<T>) -> Vec<Vec<Integer>>
+where
+    T: Clone,
+    Integer: From<T>,
+{
+    g.gram.iter().map(|i| Vector::from(i).vec).collect()
+}
+
+impl<T> Lattice<T>
+where
+    T: Ring,
+    Integer: From<T>,
+{
+    /// The Gram matrix of a basis of L(B), the HNF of a generating set
+    fn basis_gram(&self) -> GramLattice<Integer> {
+        let b = Lattice::<Integer>::from(self);
+        let g = b.gram();
+        match g.rank() == b.basis.len() {
+            true => g,
+            false => b.canonical().gram(),
+        }
+    }
+}
+
+impl<T> Invariants<T> for Lattice<T>
+where
+    T: Ring,
+    Integer: From<T>,
+{
+    fn rank(&self) -> usize {
+        Lattice::<Integer>::from(self).gram().rank()
+    }
+
+    fn gram_det(&self) -> Integer {
+        Lattice::<Integer>::from(self).gram().gram_det()
+    }
+
+    fn volume(&self, prec: u32) -> Float {
+        self.basis_gram().volume(prec)
+    }
+
+    fn gaussian_heuristic(&self, prec: u32) -> Float {
+        self.basis_gram().gaussian_heuristic(prec)
+    }
+
+    fn hermite_factor(&self) -> f64 {
+        let b0 = match self.basis.first() {
+            Some(b0) => Vector::<Integer>::from(b0),
+            None => return f64::NAN,
+        };
+        let g = self.basis_gram();
+        let b0 = Float::with_val(DEFAULT_PRECISION, &b0 * &b0);
+        let lr = log_root_volume(&g.gram_det(), g.dim(), DEFAULT_PRECISION);
+        (b0.ln() / 2u32 - lr).exp().to_f64()
+    }
+
+    fn root_hermite_factor(&self) -> f64 {
+        self.hermite_factor().powf(1.0 / self.rank() as f64)
+    }
+
+    fn gh_ratio(&self, v: &Vector<T>) -> f64 {
+        let norm = match &v.norm {
+            Some(norm) => Integer::from(norm.clone()),
+            None => {
+                let v = Vector::<Integer>::from(v);
+                &v * &v
+            }
+        };
+        let norm = Float::with_val(DEFAULT_PRECISION, norm).sqrt();
+        (norm / self.gaussian_heuristic(DEFAULT_PRECISION)).to_f64()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::*;
+    use rug::{Float, Integer};
+
+    #[test]
+    fn test_prim() {
+        let l = Lattice {
+            basis: vec![nvec![1i64, 1, 0], nvec![1, 2, 0], nvec![0, 1, 2]],
+        };
+        assert_eq!(l.rank(), 3);
+        assert_eq!(l.gram_det(), 4);
+        assert_eq!(l.gram().gram_det(), l.exact_gso().d[3]);
+
+        // Z^n has volume 1 and GH(Z^n) = Γ(n/2 + 1)^(1/n) / √π
+        let z4: Lattice<i64> = Lattice {
+            basis: vec![
+                nvec![1, 0, 0, 0],
+                nvec![0, 1, 0, 0],
+                nvec![0, 0, 1, 0],
+                nvec![0, 0, 0, 1],
+            ],
+        };
+        let gh = z4.gaussian_heuristic(53).to_f64();
+        assert!((gh - 2f64.powf(0.25) / std::f64::consts::PI.sqrt()).abs() < 1e-12);
+        assert_eq!(z4.hermite_factor(), 1.0);
+        assert!((z4.gh_ratio(&nvec![1, 0, 0, 0]) - 1.0 / gh).abs() < 1e-12);
+
+        // Rank of a generating set
+        let l = Lattice {
+            basis: vec![
+                nvec![0i64, 0, 0],
+                nvec![1, 2, 3],
+                nvec![2, 4, 6],
+                nvec![1, 0, 0],
+            ],
+        };
+        assert_eq!(l.rank(), 2);
+
+        // Volume and GH are those of the lattice generated, L((1, 0, 0), (0, 2, 3))
+        let b: Lattice<i64> = Lattice {
+            basis: vec![nvec![1, 0, 0], nvec![0, 2, 3]],
+        };
+        assert_eq!(l.volume(64), b.volume(64));
+        assert_eq!(l.gaussian_heuristic(64), b.gaussian_heuristic(64));
+        assert!(l.root_hermite_factor().is_finite());
+
+        // Inner products of i64 vectors are computed after widening
+        let l: Lattice<i64> = Lattice {
+            basis: vec![nvec![1 << 32, 0], nvec![0, 1 << 32]],
+        };
+        assert_eq!(l.rank(), 2);
+        assert_eq!(l.gram_det(), Integer::from(1) << 128);
+        assert_eq!(l.hermite_factor(), 1.0);
+        let gh = l.gaussian_heuristic(64).to_f64();
+        assert!((l.gh_ratio(&l.basis[0]) - 2f64.powi(32) / gh).abs() < 1e-9);
+
+        let l: Lattice<i64> = Lattice { basis: vec![] };
+        assert!(l.hermite_factor().is_nan());
+        assert!(l.gram().hermite_factor().is_nan());
+    }
+
+    #[test]
+    #[should_panic(expected = "linearly dependent")]
+    fn test_singular_gram() {
+        let l: Lattice<i64> = Lattice {
+            basis: vec![nvec![1, 2], nvec![2, 4]],
+        };
+        l.gram().gaussian_heuristic(64);
+    }
+
+    #[test]
+    fn test_mp() {
+        // Entries far beyond the range of an f64
+        let big: Integer = Integer::from(1) << 2000;
+        let l = Lattice {
+            basis: vec![
+                nvec![big.clone(), Integer::new()],
+                nvec![Integer::from(1), big.clone()],
+            ],
+        };
+        let det = Integer::from(&big * &big);
+        assert_eq!(l.gram_det(), Integer::from(&det * &det));
+        assert_eq!(l.volume(4096), det);
+        assert!((l.hermite_factor() - 1.0).abs() < 1e-12);
+
+        let gh = l.gaussian_heuristic(4096) / Float::with_val(4096, &big);
+        assert!((gh.to_f64() - 1.0 / std::f64::consts::PI.sqrt()).abs() < 1e-12);
+    }
+}
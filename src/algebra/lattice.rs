@@ -1,10 +1,13 @@
+use super::backend::{Field, Overflow, Ring};
 use crate::{nvec, Vector};
 use rug::{Float, Integer};
 
-const DEFAULT_PRECISION: u32 = 128;
-
 /**
-An Integer lattice generated by a full rank basis
+An Integer lattice generated by the rows of an n×m basis with n ≤ m
+
+Lattice points are the integer combinations `&z * &l` = Σ z_i b_i of the
+basis vectors. Linearly dependent generating sets are reduced to a basis
+by `LLL::lll`.
 
 # Examples
 
@@ -19,8 +22,13 @@ let l = Lattice {
 // Computes the Gram-Schmidt orthogonalization of B
 let gs : Vec<Vector<f64>> = l.gso();
 
-// Right multiplication by an n-vector corresponds to the matrix product
-let lattice_point: Vector<i64> = &l * &nvec![1, 0, 0];
+// Right multiplication by an m-vector v computes the inner products B·v
+let bv: Vector<i64> = &l * &nvec![1, 0, 0];
+assert_eq!(bv.vec, vec![1, 1, 0]);
+
+// Left multiplication by a coefficient vector combines the basis vectors
+let v: Vector<i64> = &nvec![1, -1, 1] * &l;
+assert_eq!(v.vec, vec![0, 0, 2]);
 ```
 
 Integer Lattices with arbitrary precision
@@ -41,86 +49,182 @@ let l = Lattice {
 // Computes the Gram-Schmidt orthogonalization of B
 let gs: Vec<Vector<Float>> = l.gso();
 
-// Right multiplication by an n-vector corresponds to the matrix product
-let lattice_point: Vector<Integer> = &l * &nvec![Integer::from(1), Integer::new(), Integer::new()];
+// With a chosen precision, or one adapted to the size of the basis
+let gs: Vec<Vector<Float>> = l.gso_prec(256);
+let gs: Vec<Vector<Float>> = l.gso_adaptive();
+
+// Right multiplication by an m-vector v computes the inner products B·v
+let bv: Vector<Integer> = &l * &nvec![Integer::from(1), Integer::new(), Integer::new()];
+
+// A lattice point is a combination z·B of the basis vectors
+let lattice_point: Vector<Integer> = &nvec![Integer::from(1), Integer::new(), Integer::new()] * &l;
+assert_eq!(lattice_point.vec, l.basis[0].vec);
 ```
 **/
 
-#[derive(Debug)]
-/// A `Lattice` is generated by an nxm basis
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(bound = "T: crate::SerdeScalar"))]
+/// A `Lattice` is generated by an n×m basis
 pub struct Lattice<T> {
     pub basis: Vec<Vector<T>>,
 }
 
+/// Upper bound on the precision tried by `GSO::gso_adaptive`
+const MAX_PRECISION: u32 = 1 << 16;
+
 /// Compute the Gram-Schmidt Orthogonalization of B
 pub trait GSO<T> {
+    /// Orthogonalize with the backend's default precision
     fn gso(&self) -> Vec<Vector<T>>;
+
+    /// Orthogonalize with `prec` bits of precision
+    fn gso_prec(&self, prec: u32) -> Vec<Vector<T>>;
+
+    /// Orthogonalize with a precision estimated from the basis, doubling
+    /// it until every ‖b*_i‖² is positive, or for a linearly dependent
+    /// generating set until as many are as its rank
+    fn gso_adaptive(&self) -> Vec<Vector<T>>;
+}
+
+impl<T: Ring> Lattice<T> {
+    /// Heuristic precision for the GSO of this basis
+    ///
+    /// Inner products need twice the bit length of the largest entry, and the
+    /// rounding error may grow by about one bit per basis vector.
+    pub fn estimate_precision(&self) -> u32 {
+        estimate_precision(&self.basis, 2)
+    }
 }
 
-impl GSO<f64> for Lattice<i64> {
-    fn gso(&self) -> Vec<Vector<f64>> {
-        let n = self.basis.len();
-        let m = self.basis[0].vec.len();
-        let mut mu = vec![vec![0f64; m]; n];
-        let mut gs: Vec<Vector<f64>> = vec![];
+/// Heuristic GSO precision for rows whose inner products have `factor`
+/// times the bit length of their largest entry
+pub(super) fn estimate_precision<T: Ring>(rows: &[Vector<T>], factor: u32) -> u32 {
+    let bits = rows
+        .iter()
+        .flat_map(|i| i.vec.iter().map(|j| j.bit_len()))
+        .max()
+        .unwrap_or(0);
+    factor * bits + rows.len() as u32 + 32
+}
 
-        for i in 0..self.basis.len() {
-            let x: Vec<f64> = self.basis[i].vec.iter().map(|i| *i as f64).collect();
-            gs.push(Vector {
-                vec: x,
-                norm: self.basis[i].norm.map(|z| z as f64),
-            });
+/// Orthogonalize starting from `prec` bits of precision, doubling it until
+/// every ‖b*_i‖² is positive or as many are as the exact `rank`
+pub(super) fn gso_adaptive<F, L, R>(l: &L, mut prec: u32, rank: R) -> Vec<Vector<F>>
+where
+    F: Field,
+    L: GSO<F>,
+    R: FnOnce() -> Option<usize>,
+{
+    let mut rank = Some(rank);
+    let mut exact = None;
+    loop {
+        let gs = l.gso_prec(prec);
+        let zero = F::zero(prec);
+        let positive = gs
+            .iter()
+            .filter(|i| i.norm.as_ref().unwrap() > &zero)
+            .count();
+        // Fixed precision backends cannot do any better, and neither can
+        // more precision once a division by zero produced inf or NaN
+        let fixed = zero.prec() < prec;
+        let finite = gs.iter().all(|i| i.norm.as_ref().unwrap().is_finite());
+        if positive == gs.len() || fixed || !finite || prec >= MAX_PRECISION {
+            return gs;
         }
+        // The zero ‖b*_i‖² of a dependent generating set are exact
+        let exact = exact.get_or_insert_with(|| rank.take().unwrap()());
+        if *exact == Some(positive) {
+            return gs;
+        }
+        prec *= 2;
+    }
+}
 
-        for i in 0..self.basis.len() {
-            for j in 0..i {
-                mu[i][j] = (&self.basis[j] * &gs[i]) / gs[j].norm.unwrap();
-                for k in 0..self.basis.len() {
-                    gs[i].vec[k] -= mu[i][j] * gs[j].vec[k];
-                }
+/// The rank of a Gram matrix by fraction-free elimination in T, or `None`
+/// if an intermediate value overflows
+///
+/// G is positive semidefinite, so once the remaining diagonal vanishes the
+/// remaining rows do too.
+pub(super) fn gram_rank<T: Ring>(mut g: Vec<Vec<T>>) -> Option<usize> {
+    let n = g.len();
+    let mut prev = T::one();
+    for k in 0..n {
+        let p = match (k..n).find(|i| !g[*i][*i].is_zero()) {
+            Some(p) => p,
+            None => return Some(k),
+        };
+        g.swap(k, p);
+        for row in g.iter_mut() {
+            row.swap(k, p);
+        }
+        for i in k + 1..n {
+            for j in k + 1..n {
+                // Bareiss: (g_kk·g_ij - g_ik·g_kj) / g_{k-1,k-1} is exact
+                let a = g[k][k].checked_mul(&g[i][j]).ok()?;
+                let b = g[i][k].checked_mul(&g[k][j]).ok()?;
+                g[i][j] = a.checked_sub(&b).ok()?.round_div(&prev);
             }
-            gs[i].norm = Some(&gs[i] * &gs[i]);
         }
-
-        gs
+        prev = g[k][k].clone();
     }
+    Some(n)
 }
 
-impl GSO<Float> for Lattice<Integer> {
-    fn gso(&self) -> Vec<Vector<Float>> {
-        let n = self.basis.len();
-        let m = self.basis[0].vec.len();
-        let mut mu = vec![vec![Float::new(DEFAULT_PRECISION); m]; n];
-        let mut gs: Vec<Vector<Float>> = vec![];
+impl<T: Ring> GSO<T::Field> for Lattice<T> {
+    fn gso(&self) -> Vec<Vector<T::Field>> {
+        self.gso_prec(T::PRECISION)
+    }
 
-        for i in 0..self.basis.len() {
-            let x: Vec<Float> = self.basis[i]
-                .vec
-                .iter()
-                .map(|i| Float::with_val(DEFAULT_PRECISION, i))
-                .collect();
-            gs.push(Vector {
-                vec: x,
-                norm: self.basis[i]
-                    .norm
-                    .as_ref()
-                    .map(|z| Float::with_val(DEFAULT_PRECISION, z)),
-            });
-        }
+    fn gso_prec(&self, prec: u32) -> Vec<Vector<T::Field>> {
+        let b: Vec<Vector<T::Field>> = self
+            .basis
+            .iter()
+            .map(|i| Vector {
+                vec: i.vec.iter().map(|j| j.to_field(prec)).collect(),
+                norm: None,
+            })
+            .collect();
+        let mut gs = b.clone();
 
-        for i in 0..self.basis.len() {
-            for j in 0..i {
-                mu[i][j] = (&self.basis[j] * &gs[i]) / gs[j].norm.as_ref().unwrap();
-                for k in 0..self.basis.len() {
-                    let tmp = Float::with_val(DEFAULT_PRECISION, &mu[i][j] * &gs[j].vec[k]);
-                    gs[i].vec[k] -= tmp;
+        for i in 0..gs.len() {
+            let (head, tail) = gs.split_at_mut(i);
+            let gi = &mut tail[0];
+            for (j, gj) in head.iter().enumerate() {
+                // b*_j vanishes for linearly dependent b_j
+                if gj.norm.as_ref().unwrap() == &T::Field::zero(prec) {
+                    continue;
+                }
+                let mut mu = &b[j] * &*gi;
+                mu /= gj.norm.as_ref().unwrap();
+                for k in 0..gi.vec.len() {
+                    let mut tmp = mu.clone();
+                    tmp *= &gj.vec[k];
+                    gi.vec[k] -= &tmp;
                 }
             }
-            gs[i].norm = Some(&gs[i] * &gs[i]);
+            gi.norm = Some(&*gi * &*gi);
         }
 
         gs
     }
+
+    fn gso_adaptive(&self) -> Vec<Vector<T::Field>> {
+        let rank = || {
+            let g = self
+                .basis
+                .iter()
+                .map(|a| {
+                    self.basis
+                        .iter()
+                        .map(|b| T::checked_dot(&a.vec, &b.vec).ok())
+                        .collect()
+                })
+                .collect::<Option<_>>()?;
+            gram_rank(g)
+        };
+        gso_adaptive(self, self.estimate_precision().max(T::PRECISION), rank)
+    }
 }
 
 /// Right multiply basis matrix by a vector
@@ -129,8 +233,8 @@ impl std::ops::Mul<&Vector<f64>> for &Lattice<i64> {
     type Output = Vector<i64>;
     /// Compute the matrix product with v
     fn mul(self, _rhs: &Vector<f64>) -> Vector<i64> {
-        assert_eq!(self.basis.len(), _rhs.vec.len());
-        let mut res = nvec![0i64; _rhs.vec.len()];
+        assert_eq!(self.basis[0].vec.len(), _rhs.vec.len());
+        let mut res = nvec![0i64; self.basis.len()];
         for i in 0..self.basis.len() {
             res.vec[i] = (&self.basis[i] * _rhs) as i64;
         }
@@ -139,30 +243,14 @@ impl std::ops::Mul<&Vector<f64>> for &Lattice<i64> {
     }
 }
 
-/// Right multiply basis matrix by a vector
-impl std::ops::Mul<&Vector<i64>> for &Lattice<i64> {
-    /// The resulting vector type of the matrix product
-    type Output = Vector<i64>;
-    /// Compute the matrix product with v
-    fn mul(self, _rhs: &Vector<i64>) -> Vector<i64> {
-        assert_eq!(self.basis.len(), _rhs.vec.len());
-        let mut res = nvec![0i64; _rhs.vec.len()];
-        for i in 0..self.basis.len() {
-            res.vec[i] = &self.basis[i] * _rhs;
-        }
-        res.norm = Some(&res * &res);
-        res
-    }
-}
-
 /// Right multiply basis matrix by a vector with arbitrary precision
 impl std::ops::Mul<&Vector<Float>> for &Lattice<Integer> {
     /// The resulting vector type of the matrix product
     type Output = Vector<Integer>;
     /// Compute the matrix product with v
     fn mul(self, _rhs: &Vector<Float>) -> Vector<Integer> {
-        assert_eq!(self.basis.len(), _rhs.vec.len());
-        let mut res = nvec![Integer::new(); _rhs.vec.len()];
+        assert_eq!(self.basis[0].vec.len(), _rhs.vec.len());
+        let mut res = nvec![Integer::new(); self.basis.len()];
         for i in 0..self.basis.len() {
             res.vec[i] = (&self.basis[i] * _rhs).to_integer().unwrap();
         }
@@ -171,14 +259,80 @@ impl std::ops::Mul<&Vector<Float>> for &Lattice<Integer> {
     }
 }
 
-/// Right multiply basis matrix by a vector with arbitrary precision
-impl std::ops::Mul<&Vector<Integer>> for &Lattice<Integer> {
+impl<T: Ring> Lattice<T> {
+    /// Right multiply basis matrix by a vector, failing instead of overflowing
+    pub fn checked_mul(&self, v: &Vector<T>) -> Result<Vector<T>, Overflow> {
+        assert_eq!(self.basis[0].vec.len(), v.vec.len());
+        let mut res = nvec![T::zero(); self.basis.len()];
+        for i in 0..self.basis.len() {
+            res.vec[i] = T::checked_dot(&self.basis[i].vec, &v.vec)?;
+        }
+        res.norm = Some(T::checked_dot(&res.vec, &res.vec)?);
+        Ok(res)
+    }
+
+    /// Combine the basis vectors Σ z_i b_i, failing instead of overflowing
+    pub fn checked_combine(&self, z: &Vector<T>) -> Result<Vector<T>, Overflow> {
+        assert_eq!(self.basis.len(), z.vec.len());
+        let mut res = nvec![T::zero(); self.basis[0].vec.len()];
+        for (zi, b) in z.vec.iter().zip(&self.basis) {
+            for (r, x) in res.vec.iter_mut().zip(&b.vec) {
+                *r = r.checked_add(&x.checked_mul(zi)?)?;
+            }
+        }
+        res.norm = Some(T::checked_dot(&res.vec, &res.vec)?);
+        Ok(res)
+    }
+}
+
+/// Widen a machine integer lattice to arbitrary precision
+impl<T> From<&Lattice<T>> for Lattice<Integer>
+where
+    T: Clone,
+    Integer: From<T>,
+{
+    fn from(l: &Lattice<T>) -> Self {
+        Lattice {
+            basis: l.basis.iter().map(Vector::from).collect(),
+        }
+    }
+}
+
+impl Lattice<Integer> {
+    /// Narrow to a machine integer lattice, or `None` if an entry or a
+    /// squared norm does not fit
+    pub fn narrow<T>(&self) -> Option<Lattice<T>>
+    where
+        T: for<'a> TryFrom<&'a Integer>,
+    {
+        let basis = self
+            .basis
+            .iter()
+            .map(|v| {
+                let vec = v
+                    .vec
+                    .iter()
+                    .map(|x| T::try_from(x).ok())
+                    .collect::<Option<Vec<_>>>()?;
+                let norm = match &v.norm {
+                    Some(x) => Some(T::try_from(x).ok()?),
+                    None => None,
+                };
+                Some(Vector { vec, norm })
+            })
+            .collect::<Option<Vec<_>>>()?;
+        Some(Lattice { basis })
+    }
+}
+
+/// Right multiply basis matrix by a vector
+impl<T: Ring> std::ops::Mul<&Vector<T>> for &Lattice<T> {
     /// The resulting vector type of the matrix product
-    type Output = Vector<Integer>;
+    type Output = Vector<T>;
     /// Compute the matrix product with v
-    fn mul(self, _rhs: &Vector<Integer>) -> Vector<Integer> {
-        assert_eq!(self.basis.len(), _rhs.vec.len());
-        let mut res = nvec![Integer::new(); _rhs.vec.len()];
+    fn mul(self, _rhs: &Vector<T>) -> Vector<T> {
+        assert_eq!(self.basis[0].vec.len(), _rhs.vec.len());
+        let mut res = nvec![T::zero(); self.basis.len()];
         for i in 0..self.basis.len() {
             res.vec[i] = &self.basis[i] * _rhs;
         }
@@ -187,28 +341,66 @@ impl std::ops::Mul<&Vector<Integer>> for &Lattice<Integer> {
     }
 }
 
+/// Left multiply basis matrix by a coefficient vector
+impl<T: Ring> std::ops::Mul<&Lattice<T>> for &Vector<T> {
+    /// The resulting lattice vector Σ z_i b_i
+    type Output = Vector<T>;
+    /// Compute the matrix product with z
+    fn mul(self, _rhs: &Lattice<T>) -> Vector<T> {
+        assert_eq!(self.vec.len(), _rhs.basis.len());
+        let mut res = nvec![T::zero(); _rhs.basis[0].vec.len()];
+        for (z, b) in self.vec.iter().zip(&_rhs.basis) {
+            for (r, x) in res.vec.iter_mut().zip(&b.vec) {
+                let mut tmp = x.clone();
+                tmp *= z;
+                *r += &tmp;
+            }
+        }
+        res.norm = Some(&res * &res);
+        res
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use crate::*;
-    use rug::Integer;
+    use rug::{Float, Integer};
 
     #[test]
     fn test_prim() {
         let l = Lattice {
-            basis: vec![nvec![1, 1, 0], nvec![1, 2, 0], nvec![0, 1, 2]],
+            basis: vec![nvec![1i64, 1, 0], nvec![1, 2, 0], nvec![0, 1, 2]],
         };
         let gs = l.gso();
         let ns: f64 = gs.iter().map(|i| i.norm.unwrap()).sum();
         assert_eq!(ns, 6.5);
 
         let l = Lattice {
-            basis: vec![nvec![1, -1, 1], nvec![1, 0, 1], nvec![1, 1, 2]],
+            basis: vec![nvec![1i64, -1, 1], nvec![1, 0, 1], nvec![1, 1, 2]],
         };
         let gs = l.gso();
         let ns: f64 = gs.iter().map(|i| i.norm.unwrap()).sum();
         assert_eq!(ns.round(), 4.0);
     }
 
+    #[test]
+    #[cfg(feature = "int-backends")]
+    fn test_wide() {
+        let l: Lattice<i128> = Lattice {
+            basis: vec![nvec![1 << 70, 1, 0], nvec![1, 2, 0], nvec![0, 1, 2]],
+        };
+        let gs = l.gso();
+        assert_eq!(gs[0].norm.unwrap(), 2f64.powi(140));
+        assert_eq!((&l * &nvec![0, 1, 0]).vec, vec![1, 2, 1]);
+
+        let l: Lattice<i32> = Lattice {
+            basis: vec![nvec![1, 1, 0], nvec![1, 2, 0], nvec![0, 1, 2]],
+        };
+        let gs = l.gso();
+        let ns: f64 = gs.iter().map(|i| i.norm.unwrap()).sum();
+        assert_eq!(ns, 6.5);
+    }
+
     #[test]
     fn test_mp() {
         let mut l = Lattice {
@@ -248,5 +440,52 @@ mod tests {
             sum += gs[i].norm.as_ref().unwrap();
         }
         assert_eq!(sum.round(), 4);
+
+        let gs = l.gso_prec(300);
+        assert_eq!(gs[2].vec[0].prec(), 300);
+    }
+
+    #[test]
+    fn test_adaptive() {
+        // Nearly parallel vectors with 200-bit entries
+        let big: Integer = Integer::from(1) << 200;
+        let l = Lattice {
+            basis: vec![
+                nvec![big.clone() + 1, big.clone()],
+                nvec![big.clone(), big.clone() - 1],
+            ],
+        };
+
+        // 128 bits cannot even represent the entries exactly
+        let gs = l.gso();
+        assert!(gs[1].norm.as_ref().unwrap() <= &0);
+
+        let gs = l.gso_adaptive();
+        let det2 = Float::with_val(
+            64,
+            gs[0].norm.as_ref().unwrap() * gs[1].norm.as_ref().unwrap(),
+        );
+        assert_eq!(det2.round(), 1);
+        assert!(gs[0].vec[0].prec() >= l.estimate_precision());
+
+        // ‖b*_1‖² = 0 is exact for a generating set of rank 2
+        let l = Lattice {
+            basis: vec![
+                nvec![Integer::from(1), Integer::from(2), Integer::from(3)],
+                nvec![Integer::from(2), Integer::from(4), Integer::from(6)],
+                nvec![Integer::from(1), Integer::new(), Integer::new()],
+            ],
+        };
+        let gs = l.gso_adaptive();
+        assert_eq!(gs[1].norm.as_ref().unwrap(), &0);
+        assert_eq!(gs[0].vec[0].prec(), l.estimate_precision().max(128));
+        let gs = l.gram().gso_adaptive();
+        assert_eq!(gs[1].norm.as_ref().unwrap(), &0);
+        assert!(gs[2].norm.as_ref().unwrap().is_finite());
+        assert!(gs[2].norm.as_ref().unwrap() > &0);
+        assert_eq!(gs[0].vec[0].prec(), l.gram().estimate_precision().max(128));
+
+        let l: Lattice<i64> = Lattice { basis: vec![] };
+        assert!(l.gso_adaptive().is_empty());
     }
 }
@@ -1,6 +1,28 @@
+mod backend;
+mod coordinates;
+mod dual;
+mod exact;
+mod gram;
+mod invariants;
 mod lattice;
+mod normal_form;
+mod profile;
+#[cfg(feature = "serde")]
+mod serialize;
+mod sublattice;
 mod vector;
 
+#[allow(unused_imports)]
 pub use crate::nvec;
+pub use backend::{Field, Overflow, Ring, Scalar, DEFAULT_PRECISION};
+pub use coordinates::Coordinates;
+pub use dual::{Dual, DualBasis};
+pub use exact::{ExactGSO, IntegralGSO};
+pub use gram::GramLattice;
+pub use invariants::Invariants;
 pub use lattice::{Lattice, GSO};
+pub use normal_form::{HermiteForm, NormalForm, SmithForm};
+pub use profile::{Profile, QualityProfile};
+#[cfg(feature = "serde")]
+pub use serialize::SerdeScalar;
 pub use vector::{GaussReduce, Vector};
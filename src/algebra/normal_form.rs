@@ -0,0 +1,461 @@
+use super::lattice::Lattice;
+use crate::{nvec, Vector};
+use rug::Integer;
+
+/**
+
+Hermite and Smith normal forms of integer bases
+
+The Hermite normal form H = U·B is the unique echelon basis of L(B) with
+positive pivots and the entries above each pivot reduced to `[0, pivot)`, so
+two bases span the same lattice exactly when their HNFs agree. Linearly
+dependent rows of B become zero rows of U·B and are dropped from H, their
+rows of U are the integer relations between the vectors of B.
+
+The Smith normal form D = U·B·V is diagonal with d_0 | d_1 | .., and gives
+the structure of quotients of lattices.
+
+# Examples
+
+```rust
+use rug::Integer;
+use svp::{nvec, Lattice, NormalForm, Vector};
+
+let l = Lattice {
+    basis: vec![
+        nvec![Integer::from(2), Integer::from(4), Integer::from(4)],
+        nvec![Integer::from(-6), Integer::from(6), Integer::from(12)],
+        nvec![Integer::from(10), Integer::from(-4), Integer::from(-16)],
+    ],
+};
+
+let hnf = l.hnf();
+assert_eq!(hnf.h.basis[0].vec, vec![2, 4, 4]);
+assert_eq!(hnf.h.basis[1].vec, vec![0, 6, 0]);
+assert_eq!(hnf.h.basis[2].vec, vec![0, 0, 12]);
+
+// H = U·B
+for (h, u) in hnf.h.basis.iter().zip(&hnf.u) {
+    assert_eq!((u * &l).vec, h.vec);
+}
+
+// D = U·B·V
+let snf = l.snf();
+assert_eq!(snf.d, vec![2, 6, 12]);
+
+// Bases of the same lattice
+let m = Lattice {
+    basis: vec![
+        nvec![Integer::from(12), Integer::from(0), Integer::from(-12)],
+        nvec![Integer::from(-8), Integer::from(2), Integer::from(8)],
+        nvec![Integer::from(10), Integer::from(-4), Integer::from(-16)],
+    ],
+};
+assert!(l.same_lattice(&m));
+
+// 2L is a sublattice of index 8 in L, with L/2L ≅ (Z/2Z)^3
+let double = Lattice {
+    basis: l.basis.iter().map(|b| b * Integer::from(2)).collect(),
+};
+assert_eq!(l.quotient(&double), Some(vec![Integer::from(2); 3]));
+```
+
+Linear dependencies are removed from generating sets
+
+```rust
+use rug::Integer;
+use svp::{nvec, Lattice, NormalForm, Vector};
+
+let l = Lattice {
+    basis: vec![
+        nvec![Integer::from(2), Integer::from(0)],
+        nvec![Integer::from(3), Integer::from(0)],
+        nvec![Integer::from(0), Integer::from(5)],
+    ],
+};
+let hnf = l.hnf();
+assert_eq!(hnf.h.basis.len(), 2);
+assert_eq!(hnf.h.basis[0].vec, vec![1, 0]);
+
+// The last row of U is a relation between the generators
+assert_eq!((&hnf.u[2] * &l).vec, vec![0, 0]);
+```
+**/
+
+#[derive(Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct HermiteForm {
+    pub h: Lattice<Integer>,     // nonzero rows of U·B
+    pub u: Vec<Vector<Integer>>, // unimodular n×n transformation
+}
+
+#[derive(Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct SmithForm {
+    #[cfg_attr(feature = "serde", serde(with = "crate::algebra::serialize::entries"))]
+    pub d: Vec<Integer>, // diagonal of U·B·V, nonzero entries first
+    pub u: Vec<Vector<Integer>>, // unimodular n×n row transformation
+    pub v: Vec<Vector<Integer>>, // unimodular m×m column transformation
+}
+
+/// Compute the Hermite and Smith normal forms of a basis
+pub trait NormalForm {
+    fn hnf(&self) -> HermiteForm;
+
+    fn snf(&self) -> SmithForm;
+}
+
+/// The n×n identity matrix
+fn identity(n: usize) -> Vec<Vector<Integer>> {
+    (0..n)
+        .map(|i| {
+            let mut e = nvec![Integer::new(); n];
+            e.vec[i] = Integer::from(1);
+            e
+        })
+        .collect()
+}
+
+/// Replace rows (a, b) by (s·a + t·b, x·a + y·b)
+fn combine_rows(m: &mut [Vector<Integer>], a: usize, b: usize, c: [&Integer; 4]) {
+    for k in 0..m[a].vec.len() {
+        let ra = Integer::from(c[0] * &m[a].vec[k]) + c[1] * &m[b].vec[k];
+        let rb = Integer::from(c[2] * &m[a].vec[k]) + c[3] * &m[b].vec[k];
+        m[a].vec[k] = ra;
+        m[b].vec[k] = rb;
+    }
+}
+
+/// Subtract q times row b from row a
+fn sub_row(m: &mut [Vector<Integer>], a: usize, b: usize, q: &Integer) {
+    for k in 0..m[a].vec.len() {
+        let x = Integer::from(q * &m[b].vec[k]);
+        m[a].vec[k] -= x;
+    }
+}
+
+/// Subtract q times column b from column a
+fn sub_col(m: &mut [Vector<Integer>], a: usize, b: usize, q: &Integer) {
+    for row in m.iter_mut() {
+        let x = Integer::from(q * &row.vec[b]);
+        row.vec[a] -= x;
+    }
+}
+
+fn negate_row(m: &mut [Vector<Integer>], a: usize) {
+    for x in m[a].vec.iter_mut() {
+        *x = -x.clone();
+    }
+}
+
+/// The HNF of the rows of `b`, along with the unimodular U such that U·b = H
+fn hermite(b: &[Vector<Integer>]) -> (Vec<Vector<Integer>>, Vec<Vector<Integer>>) {
+    let n = b.len();
+    let m = b.first().map_or(0, |r| r.vec.len());
+    let mut h: Vec<Vector<Integer>> = b.to_vec();
+    let mut u = identity(n);
+    let mut r = 0;
+
+    for col in 0..m {
+        if r == n {
+            break;
+        }
+        // Accumulate the gcd of the column in row r
+        for i in r + 1..n {
+            if h[i].vec[col] == 0 {
+                continue;
+            }
+            let (g, s, t) = h[r].vec[col]
+                .clone()
+                .extended_gcd(h[i].vec[col].clone(), Integer::new());
+            let x = -Integer::from(&h[i].vec[col] / &g);
+            let y = Integer::from(&h[r].vec[col] / &g);
+            combine_rows(&mut h, r, i, [&s, &t, &x, &y]);
+            combine_rows(&mut u, r, i, [&s, &t, &x, &y]);
+        }
+        if h[r].vec[col] == 0 {
+            continue;
+        }
+        if h[r].vec[col] < 0 {
+            negate_row(&mut h, r);
+            negate_row(&mut u, r);
+        }
+        // Reduce the entries above the pivot
+        for k in 0..r {
+            let q = h[k].vec[col].clone().div_rem_floor(h[r].vec[col].clone()).0;
+            sub_row(&mut h, k, r, &q);
+            sub_row(&mut u, k, r, &q);
+        }
+        r += 1;
+    }
+
+    h.truncate(r);
+    for v in h.iter_mut() {
+        v.norm = Some(&*v * &*v);
+    }
+    (h, u)
+}
+
+/// Coefficients x with x·H = v for an HNF basis H, or `None` if v ∉ L(H)
+pub(super) fn hnf_coords(h: &[Vector<Integer>], v: &Vector<Integer>) -> Option<Vector<Integer>> {
+    let mut res = v.vec.clone();
+    let mut x = nvec![Integer::new(); h.len()];
+    let mut col = 0;
+    for (i, hi) in h.iter().enumerate() {
+        while hi.vec[col] == 0 {
+            col += 1;
+        }
+        let (q, rem) = res[col].clone().div_rem(hi.vec[col].clone());
+        if rem != 0 {
+            return None;
+        }
+        for k in col..res.len() {
+            res[k] -= Integer::from(&q * &hi.vec[k]);
+        }
+        x.vec[i] = q;
+    }
+    match res.iter().all(|i| *i == 0) {
+        true => Some(x),
+        false => None,
+    }
+}
+
+/// The SNF diagonal of the rows of `b`, along with U and V such that U·b·V = D
+fn smith(b: &[Vector<Integer>]) -> SmithForm {
+    let n = b.len();
+    let m = b.first().map_or(0, |r| r.vec.len());
+    let mut a: Vec<Vector<Integer>> = b.to_vec();
+    let mut u = identity(n);
+    let mut v = identity(m);
+    let mut d = vec![];
+
+    for t in 0..n.min(m) {
+        loop {
+            // Move the smallest nonzero entry to (t, t)
+            let mut pivot: Option<(usize, usize)> = None;
+            for i in t..n {
+                for j in t..m {
+                    let smaller = match pivot {
+                        Some((pi, pj)) => a[i].vec[j].cmp_abs(&a[pi].vec[pj]).is_lt(),
+                        None => true,
+                    };
+                    if a[i].vec[j] != 0 && smaller {
+                        pivot = Some((i, j));
+                    }
+                }
+            }
+            let (pi, pj) = match pivot {
+                Some(p) => p,
+                None => break,
+            };
+            a.swap(t, pi);
+            u.swap(t, pi);
+            for row in a.iter_mut().chain(v.iter_mut()) {
+                row.vec.swap(t, pj);
+            }
+
+            // Reduce the pivot row and column
+            let mut done = true;
+            for i in t + 1..n {
+                let q = Integer::from(&a[i].vec[t] / &a[t].vec[t]);
+                sub_row(&mut a, i, t, &q);
+                sub_row(&mut u, i, t, &q);
+                done &= a[i].vec[t] == 0;
+            }
+            for j in t + 1..m {
+                let q = Integer::from(&a[t].vec[j] / &a[t].vec[t]);
+                sub_col(&mut a, j, t, &q);
+                sub_col(&mut v, j, t, &q);
+                done &= a[t].vec[j] == 0;
+            }
+            if !done {
+                continue;
+            }
+
+            // The pivot must divide every remaining entry
+            let bad =
+                (t + 1..n).find(|i| (t + 1..m).any(|j| !a[*i].vec[j].is_divisible(&a[t].vec[t])));
+            match bad {
+                Some(i) => {
+                    let one = Integer::from(1);
+                    sub_row(&mut a, t, i, &-one.clone());
+                    sub_row(&mut u, t, i, &-one);
+                }
+                None => break,
+            }
+        }
+        if a[t].vec[t] < 0 {
+            negate_row(&mut a, t);
+            negate_row(&mut u, t);
+        }
+        d.push(a[t].vec[t].clone());
+    }
+
+    SmithForm { d, u, v }
+}
+
+impl NormalForm for Lattice<Integer> {
+    fn hnf(&self) -> HermiteForm {
+        let (h, u) = hermite(&self.basis);
+        HermiteForm {
+            h: Lattice { basis: h },
+            u,
+        }
+    }
+
+    fn snf(&self) -> SmithForm {
+        smith(&self.basis)
+    }
+}
+
+impl Lattice<Integer> {
+    /// The canonical basis of L(B), i.e. its Hermite normal form
+    pub fn canonical(&self) -> Lattice<Integer> {
+        self.hnf().h
+    }
+
+    /// Test whether both bases span the same lattice
+    pub fn same_lattice(&self, other: &Lattice<Integer>) -> bool {
+        let (a, b) = (self.canonical(), other.canonical());
+        a.basis.len() == b.basis.len() && a.basis.iter().zip(&b.basis).all(|(x, y)| x.vec == y.vec)
+    }
+
+    /// The invariant factors of L / L' for a sublattice L' = L(sub), or
+    /// `None` if L' is not contained in L
+    ///
+    /// L / L' ≅ Z/d_0 ⊕ .. ⊕ Z/d_k, where trivial factors are omitted and
+    /// each free summand Z is reported as a factor 0.
+    pub fn quotient(&self, sub: &Lattice<Integer>) -> Option<Vec<Integer>> {
+        let h = self.canonical();
+        let coords = sub
+            .basis
+            .iter()
+            .map(|v| hnf_coords(&h.basis, v))
+            .collect::<Option<Vec<_>>>()?;
+
+        let d = match coords.is_empty() {
+            true => vec![],
+            false => smith(&coords).d,
+        };
+        let rank = d.iter().filter(|i| **i != 0).count();
+        let mut res: Vec<Integer> = d.into_iter().filter(|i| *i > 1).collect();
+        res.extend((rank..h.basis.len()).map(|_| Integer::new()));
+        Some(res)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::*;
+    use rug::Integer;
+
+    fn lattice(b: &[&[i64]]) -> Lattice<Integer> {
+        Lattice {
+            basis: b
+                .iter()
+                .map(|i| Vector {
+                    vec: i.iter().map(|j| Integer::from(*j)).collect(),
+                    norm: None,
+                })
+                .collect(),
+        }
+    }
+
+    fn mul(a: &[Vector<Integer>], b: &[Vector<Integer>]) -> Vec<Vec<Integer>> {
+        let l = Lattice { basis: b.to_vec() };
+        a.iter().map(|i| (i * &l).vec).collect()
+    }
+
+    #[test]
+    fn test_hnf() {
+        let l = lattice(&[&[3, 1, 4, 1], &[5, 9, 2, 6], &[5, 3, 5, 8], &[9, 7, 9, 3]]);
+        let hnf = l.hnf();
+        assert_eq!(hnf.h.basis.len(), 4);
+        assert_eq!(
+            mul(&hnf.u, &l.basis),
+            hnf.h
+                .basis
+                .iter()
+                .map(|i| i.vec.clone())
+                .collect::<Vec<_>>()
+        );
+
+        // Echelon form with reduced entries above the pivots
+        for (i, h) in hnf.h.basis.iter().enumerate() {
+            assert!(h.vec[..i].iter().all(|x| *x == 0) && h.vec[i] > 0);
+            for k in 0..i {
+                assert!(hnf.h.basis[k].vec[i] >= 0 && hnf.h.basis[k].vec[i] < h.vec[i]);
+            }
+        }
+
+        // |det U| = 1 and |det H| = |det B|
+        assert_eq!(
+            Lattice {
+                basis: hnf.u.clone()
+            }
+            .gram_det(),
+            1
+        );
+        assert_eq!(hnf.h.gram_det(), l.gram_det());
+
+        // The canonical basis does not depend on the input basis
+        let v = lattice(&[&[1, 1, 0, 0], &[0, 1, 0, 0], &[0, 0, 1, 0], &[0, 0, -3, 1]]);
+        let m = Lattice {
+            basis: mul(&v.basis, &l.basis)
+                .into_iter()
+                .map(|i| Vector { vec: i, norm: None })
+                .collect(),
+        };
+        assert!(l.same_lattice(&m));
+        assert!(!l.same_lattice(&lattice(&[
+            &[1, 0, 0, 0],
+            &[0, 1, 0, 0],
+            &[0, 0, 1, 0],
+            &[0, 0, 0, 1]
+        ])));
+    }
+
+    #[test]
+    fn test_snf() {
+        let l = lattice(&[&[2, 4, 4], &[-6, 6, 12], &[10, -4, -16]]);
+        let snf = l.snf();
+        assert_eq!(snf.d, vec![2, 6, 12]);
+
+        let d = mul(&snf.u, &mul_cols(&l.basis, &snf.v));
+        for i in 0..3 {
+            for j in 0..3 {
+                assert_eq!(
+                    d[i][j],
+                    if i == j {
+                        snf.d[i].clone()
+                    } else {
+                        Integer::new()
+                    }
+                );
+            }
+        }
+
+        // A rank deficient generating set
+        let l = lattice(&[&[2, 0], &[3, 0], &[0, 0]]);
+        assert_eq!(l.snf().d, vec![1, 0]);
+    }
+
+    fn mul_cols(b: &[Vector<Integer>], v: &[Vector<Integer>]) -> Vec<Vector<Integer>> {
+        let l = Lattice { basis: v.to_vec() };
+        b.iter().map(|i| i * &l).collect()
+    }
+
+    #[test]
+    fn test_quotient() {
+        let l = lattice(&[&[1, 0, 0], &[0, 1, 0], &[0, 0, 1]]);
+        let s = lattice(&[&[2, 0, 0], &[0, 6, 0]]);
+        assert_eq!(
+            l.quotient(&s),
+            Some(vec![Integer::from(2), Integer::from(6), Integer::new()])
+        );
+
+        // Not a sublattice
+        assert_eq!(s.quotient(&l), None);
+        assert_eq!(s.quotient(&s), Some(vec![]));
+    }
+}
@@ -0,0 +1,203 @@
+use super::backend::{Field, Ring, Scalar};
+use super::gram::GramLattice;
+use super::lattice::{Lattice, GSO};
+use crate::Vector;
+use std::fmt::Write;
+
+/**
+
+The shape of a basis, as tracked during reduction
+
+Every quantity is derived from the squared norms ‖b*_i‖² of `GSO::gso_adaptive`
+and is kept in natural logarithms, since defects and potentials quickly
+exceed the range of an `f64`. The potential is Π ‖b*_i‖^(2(n - i)), which
+decreases with every LLL swap.
+
+# Examples
+
+```rust
+use svp::{nvec, Lattice, QualityProfile, Vector};
+
+let l = Lattice {
+    basis: vec![nvec![1i64, 1, 0], nvec![1, 2, 0], nvec![0, 1, 2]],
+};
+
+let p = l.profile();
+
+// ln‖b*_i‖ for ‖b*_i‖² = 2, 1/2, 4
+assert!((p.log_norms[1] - 0.5f64.ln() / 2.0).abs() < 1e-12);
+
+// ‖b_0‖·‖b_1‖·‖b_2‖ / vol(L) = √50 / 2
+assert!((p.log_defect - 12.5f64.ln() / 2.0).abs() < 1e-12);
+
+// One line per basis vector, along with the fitted GSA line
+let csv = p.to_csv();
+assert!(csv.starts_with("i,log_norm,gsa\n0,"));
+assert_eq!(csv.lines().count(), 4);
+```
+**/
+
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Profile {
+    pub log_norms: Vec<f64>, // ln‖b*_i‖
+    pub slope: f64,          // least squares fit ln‖b*_i‖ ≈ intercept + slope * i
+    pub intercept: f64,
+    pub log_defect: f64,    // ln(Π ‖b_i‖ / vol(L))
+    pub log_potential: f64, // ln(Π ‖b*_i‖^(2(n - i)))
+}
+
+/// Compute the `Profile` of a basis
+pub trait QualityProfile {
+    fn profile(&self) -> Profile;
+}
+
+impl Profile {
+    /// Build the profile from ‖b*_i‖² and ‖b_i‖² given as natural logarithms
+    fn new(log_gs: Vec<f64>, log_b: Vec<f64>) -> Self {
+        let n = log_gs.len();
+        let log_norms: Vec<f64> = log_gs.iter().map(|x| x / 2.0).collect();
+
+        let mean_i = (n as f64 - 1.0) / 2.0;
+        let mean_y = log_norms.iter().sum::<f64>() / n.max(1) as f64;
+        let (mut sxy, mut sxx) = (0.0, 0.0);
+        for (i, y) in log_norms.iter().enumerate() {
+            sxy += (i as f64 - mean_i) * (y - mean_y);
+            sxx += (i as f64 - mean_i).powi(2);
+        }
+        let slope = match sxx > 0.0 {
+            true => sxy / sxx,
+            false => 0.0,
+        };
+
+        let log_defect = (log_b.iter().sum::<f64>() - log_gs.iter().sum::<f64>()) / 2.0;
+        let log_potential = log_gs
+            .iter()
+            .enumerate()
+            .map(|(i, x)| (n - i) as f64 * x)
+            .sum();
+
+        Self {
+            log_norms,
+            slope,
+            intercept: mean_y - slope * mean_i,
+            log_defect,
+            log_potential,
+        }
+    }
+
+    /// Render the profile as CSV with columns `i`, `log_norm` and `gsa`
+    pub fn to_csv(&self) -> String {
+        let mut csv = String::from("i,log_norm,gsa\n");
+        for (i, y) in self.log_norms.iter().enumerate() {
+            let fit = self.intercept + self.slope * i as f64;
+            writeln!(csv, "{},{},{}", i, y, fit).unwrap();
+        }
+        csv
+    }
+}
+
+/// Natural logarithms of the Gram-Schmidt squared norms
+fn log_gso_norms<T: Field>(gs: &[Vector<T>]) -> Vec<f64> {
+    gs.iter()
+        .map(|i| i.norm.as_ref().unwrap().ln().to_f64())
+        .collect()
+}
+
+impl<T: Ring> QualityProfile for Lattice<T> {
+    fn profile(&self) -> Profile {
+        let gs = self.gso_adaptive();
+        let prec = gs
+            .first()
+            .map_or(T::PRECISION, |i| i.norm.as_ref().unwrap().prec());
+        let log_b = self
+            .basis
+            .iter()
+            .map(|i| {
+                // Squared in the field, i·i may overflow a machine integer
+                let v: Vec<T::Field> = i.vec.iter().map(|x| x.to_field(prec)).collect();
+                T::Field::dot(&v, &v).ln().to_f64()
+            })
+            .collect();
+        Profile::new(log_gso_norms(&gs), log_b)
+    }
+}
+
+impl<T: Ring> QualityProfile for GramLattice<T> {
+    fn profile(&self) -> Profile {
+        let gs = self.gso_adaptive();
+        let prec = gs
+            .first()
+            .map_or(T::PRECISION, |i| i.norm.as_ref().unwrap().prec());
+        let log_b = (0..self.dim())
+            .map(|i| self.gram[i].vec[i].to_field(prec).ln().to_f64())
+            .collect();
+        Profile::new(log_gso_norms(&gs), log_b)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::*;
+    use rug::Integer;
+
+    #[test]
+    fn test_prim() {
+        let l: Lattice<i64> = Lattice {
+            basis: vec![nvec![1, 0, 0], nvec![0, 1, 0], nvec![0, 0, 1]],
+        };
+        let p = l.profile();
+        assert_eq!(p.log_norms, vec![0.0; 3]);
+        assert_eq!((p.slope, p.log_defect, p.log_potential), (0.0, 0.0, 0.0));
+
+        // A geometric profile is fitted exactly
+        let l = Lattice {
+            basis: vec![nvec![8i64, 0, 0], nvec![1, 4, 0], nvec![3, 1, 2]],
+        };
+        let p = l.profile();
+        assert!((p.slope + 2f64.ln()).abs() < 1e-12);
+        assert!((p.intercept - 8f64.ln()).abs() < 1e-12);
+
+        // ln(8^6 · 4^4 · 2^2)
+        assert!((p.log_potential - 28.0 * 2f64.ln()).abs() < 1e-12);
+
+        // Consistent with the Gram matrix
+        let q = l.gram().profile();
+        assert!((p.log_defect - q.log_defect).abs() < 1e-12);
+        let row: Vec<f64> = p
+            .to_csv()
+            .lines()
+            .nth(3)
+            .unwrap()
+            .split(',')
+            .map(|x| x.parse().unwrap())
+            .collect();
+        assert!((row[1] - 2f64.ln()).abs() < 1e-12 && (row[2] - 2f64.ln()).abs() < 1e-12);
+
+        let l: Lattice<i64> = Lattice { basis: vec![] };
+        assert!(l.profile().log_norms.is_empty());
+
+        // ‖b_0‖² = 2^64 does not fit in an i64, the basis is orthogonal
+        let l: Lattice<i64> = Lattice {
+            basis: vec![nvec![1 << 32, 0], nvec![0, 1]],
+        };
+        let p = l.profile();
+        assert!(p.log_defect.abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_mp() {
+        // Squared norms far beyond the range of an f64
+        let big: Integer = Integer::from(1) << 1200;
+        let l = Lattice {
+            basis: vec![
+                nvec![big.clone(), Integer::new()],
+                nvec![big.clone(), Integer::from(1)],
+            ],
+        };
+        let p = l.profile();
+        assert!((p.log_norms[0] - 1200.0 * 2f64.ln()).abs() < 1e-9);
+        assert!(p.log_norms[1].abs() < 1e-9);
+        assert!((p.log_defect - 1200.0 * 2f64.ln()).abs() < 1e-9);
+    }
+}
@@ -0,0 +1,264 @@
+use rug::{Float, Integer};
+use serde::de::Error;
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+/**
+
+Serde support for lattice entries, enabled by the `serde` feature
+
+Machine integers and floats are written as plain numbers, while
+`rug::Integer` entries are written as decimal strings, so they survive
+formats such as JSON whose numbers are doubles. A `rug::Float` is written
+along with its precision, with enough decimal digits to read it back
+exactly. Machine floats round-trip exactly through JSON with serde_json's
+`float_roundtrip` feature.
+
+# Examples
+
+```rust
+use rug::Integer;
+use svp::{nvec, Lattice, Vector};
+
+let l = Lattice {
+    basis: vec![nvec![Integer::from(1) << 100, Integer::from(-3)]],
+};
+let json = serde_json::to_string(&l).unwrap();
+assert_eq!(
+    json,
+    r#"{"basis":[{"vec":["1267650600228229401496703205376","-3"],"norm":null}]}"#
+);
+
+let r: Lattice<Integer> = serde_json::from_str(&json).unwrap();
+assert_eq!(r.basis[0].vec, l.basis[0].vec);
+```
+**/
+pub trait SerdeScalar: Sized {
+    fn serialize_scalar<S: Serializer>(&self, s: S) -> Result<S::Ok, S::Error>;
+
+    fn deserialize_scalar<'de, D: Deserializer<'de>>(d: D) -> Result<Self, D::Error>;
+}
+
+macro_rules! native {
+    ($($t:ty),*) => ($(
+        impl SerdeScalar for $t {
+            fn serialize_scalar<S: Serializer>(&self, s: S) -> Result<S::Ok, S::Error> {
+                self.serialize(s)
+            }
+
+            fn deserialize_scalar<'de, D: Deserializer<'de>>(d: D) -> Result<Self, D::Error> {
+                <$t>::deserialize(d)
+            }
+        }
+    )*)
+}
+
+native!(i8, i16, i32, i64, i128, isize, f32, f64);
+
+impl SerdeScalar for Integer {
+    fn serialize_scalar<S: Serializer>(&self, s: S) -> Result<S::Ok, S::Error> {
+        s.serialize_str(&self.to_string())
+    }
+
+    fn deserialize_scalar<'de, D: Deserializer<'de>>(d: D) -> Result<Self, D::Error> {
+        let s = String::deserialize(d)?;
+        s.parse()
+            .map_err(|_| D::Error::custom(format!("`{}` is not a decimal integer", s)))
+    }
+}
+
+/// A `Float` along with its precision
+#[derive(Serialize, Deserialize)]
+struct FloatRepr {
+    prec: u32,
+    value: String,
+}
+
+impl SerdeScalar for Float {
+    fn serialize_scalar<S: Serializer>(&self, s: S) -> Result<S::Ok, S::Error> {
+        FloatRepr {
+            prec: self.prec(),
+            value: self.to_string_radix(10, None),
+        }
+        .serialize(s)
+    }
+
+    fn deserialize_scalar<'de, D: Deserializer<'de>>(d: D) -> Result<Self, D::Error> {
+        let r = FloatRepr::deserialize(d)?;
+        let x = Float::parse(&r.value)
+            .map_err(|_| D::Error::custom(format!("`{}` is not a float", r.value)))?;
+        Ok(Float::with_val(r.prec, x))
+    }
+}
+
+/// Serialize a borrowed scalar
+struct Ref<'a, T>(&'a T);
+
+impl<T: SerdeScalar> Serialize for Ref<'_, T> {
+    fn serialize<S: Serializer>(&self, s: S) -> Result<S::Ok, S::Error> {
+        self.0.serialize_scalar(s)
+    }
+}
+
+/// Deserialize an owned scalar
+struct Owned<T>(T);
+
+impl<'de, T: SerdeScalar> Deserialize<'de> for Owned<T> {
+    fn deserialize<D: Deserializer<'de>>(d: D) -> Result<Self, D::Error> {
+        T::deserialize_scalar(d).map(Owned)
+    }
+}
+
+/// `#[serde(with)]` for a single scalar field
+pub(crate) mod scalar {
+    use super::*;
+
+    pub fn serialize<T: SerdeScalar, S: Serializer>(x: &T, s: S) -> Result<S::Ok, S::Error> {
+        x.serialize_scalar(s)
+    }
+
+    pub fn deserialize<'de, T: SerdeScalar, D: Deserializer<'de>>(d: D) -> Result<T, D::Error> {
+        T::deserialize_scalar(d)
+    }
+}
+
+/// `#[serde(with)]` for an optional scalar field
+pub(crate) mod optional {
+    use super::*;
+
+    pub fn serialize<T: SerdeScalar, S: Serializer>(
+        x: &Option<T>,
+        s: S,
+    ) -> Result<S::Ok, S::Error> {
+        x.as_ref().map(Ref).serialize(s)
+    }
+
+    pub fn deserialize<'de, T: SerdeScalar, D: Deserializer<'de>>(
+        d: D,
+    ) -> Result<Option<T>, D::Error> {
+        Ok(Option::<Owned<T>>::deserialize(d)?.map(|x| x.0))
+    }
+}
+
+/// `#[serde(with)]` for a list of scalars
+pub(crate) mod entries {
+    use super::*;
+
+    pub fn serialize<T: SerdeScalar, S: Serializer>(x: &[T], s: S) -> Result<S::Ok, S::Error> {
+        s.collect_seq(x.iter().map(Ref))
+    }
+
+    pub fn deserialize<'de, T: SerdeScalar, D: Deserializer<'de>>(
+        d: D,
+    ) -> Result<Vec<T>, D::Error> {
+        Ok(Vec::<Owned<T>>::deserialize(d)?
+            .into_iter()
+            .map(|x| x.0)
+            .collect())
+    }
+}
+
+/// `#[serde(with)]` for a matrix of scalars given by its rows
+pub(crate) mod rows {
+    use super::*;
+
+    pub fn serialize<T: SerdeScalar, S: Serializer>(x: &[Vec<T>], s: S) -> Result<S::Ok, S::Error> {
+        s.collect_seq(x.iter().map(|r| r.iter().map(Ref).collect::<Vec<_>>()))
+    }
+
+    pub fn deserialize<'de, T: SerdeScalar, D: Deserializer<'de>>(
+        d: D,
+    ) -> Result<Vec<Vec<T>>, D::Error> {
+        Ok(Vec::<Vec<Owned<T>>>::deserialize(d)?
+            .into_iter()
+            .map(|r| r.into_iter().map(|x| x.0).collect())
+            .collect())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::testing;
+    use crate::*;
+    use rug::{Float, Integer};
+
+    #[test]
+    fn test_prim() {
+        let mut l = testing::dim10();
+        for v in l.basis.iter_mut() {
+            v.norm = Some(&*v * &*v);
+        }
+        let json = serde_json::to_string(&l).unwrap();
+        assert!(json.starts_with(r#"{"basis":[{"vec":[-1,0,1,0,1,0,0,0,-1,1],"norm":5}"#));
+        let r: Lattice<i64> = serde_json::from_str(&json).unwrap();
+        assert_eq!(serde_json::to_string(&r).unwrap(), json);
+
+        // Sieve output
+        let mut gs = gsieve![l.clone(), (l.basis.len() as f64).ln()];
+        let short_vecs = gs.sieve();
+        let json = serde_json::to_string(&short_vecs).unwrap();
+        let r: Vec<Vector<i64>> = serde_json::from_str(&json).unwrap();
+        for (u, v) in r.iter().zip(&short_vecs) {
+            assert_eq!((&u.vec, &u.norm), (&v.vec, &v.norm));
+        }
+
+        // Floating GSO and profile
+        let gso: Vec<Vector<f64>> = l.gso();
+        let r: Vec<Vector<f64>> =
+            serde_json::from_str(&serde_json::to_string(&gso).unwrap()).unwrap();
+        assert_eq!(r[9].vec, gso[9].vec);
+        assert_eq!(r[9].norm, gso[9].norm);
+        let p = l.profile();
+        let q: Profile = serde_json::from_str(&serde_json::to_string(&p).unwrap()).unwrap();
+        assert_eq!(q.log_norms, p.log_norms);
+        assert_eq!(q.slope, p.slope);
+
+        // Gram matrices
+        let g = l.gram();
+        let h: GramLattice<i64> =
+            serde_json::from_str(&serde_json::to_string(&g).unwrap()).unwrap();
+        assert_eq!(h.gram[3].vec, g.gram[3].vec);
+    }
+
+    #[test]
+    fn test_mp() {
+        let big: Integer = Integer::from(1) << 200;
+        let l = Lattice {
+            basis: vec![
+                nvec![big.clone() + 1, -big.clone(), Integer::from(3)],
+                nvec![Integer::new(), Integer::from(1), Integer::from(2)],
+            ],
+        };
+        let json = serde_json::to_string(&l).unwrap();
+        assert!(json.contains(&format!("\"-{}\"", big)));
+        let r: Lattice<Integer> = serde_json::from_str(&json).unwrap();
+        assert_eq!(r.basis[0].vec, l.basis[0].vec);
+
+        // Exact GSO and normal forms
+        let gs = l.exact_gso();
+        let r: IntegralGSO = serde_json::from_str(&serde_json::to_string(&gs).unwrap()).unwrap();
+        assert_eq!((r.d, r.lambda), (gs.d, gs.lambda));
+        let hnf = l.hnf();
+        let r: HermiteForm = serde_json::from_str(&serde_json::to_string(&hnf).unwrap()).unwrap();
+        assert_eq!(r.u[1].vec, hnf.u[1].vec);
+        let d = l.dual();
+        let r: DualBasis = serde_json::from_str(&serde_json::to_string(&d).unwrap()).unwrap();
+        assert_eq!(r.scale, d.scale);
+
+        // Floats keep their precision and value
+        let gso: Vec<Vector<Float>> = l.gso_prec(300);
+        let r: Vec<Vector<Float>> =
+            serde_json::from_str(&serde_json::to_string(&gso).unwrap()).unwrap();
+        assert_eq!(r[1].vec, gso[1].vec);
+        assert_eq!(r[1].norm.as_ref().unwrap().prec(), 300);
+
+        // Integers as JSON numbers are rejected
+        let err =
+            serde_json::from_str::<Lattice<Integer>>(r#"{"basis":[{"vec":[1],"norm":null}]}"#);
+        assert!(err.is_err());
+        let err = serde_json::from_str::<Vector<Integer>>(r#"{"vec":["1.5"],"norm":null}"#);
+        assert!(err
+            .unwrap_err()
+            .to_string()
+            .contains("`1.5` is not a decimal integer"));
+    }
+}
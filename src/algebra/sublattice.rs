@@ -0,0 +1,210 @@
+use super::lattice::Lattice;
+use super::normal_form::NormalForm;
+use crate::{nvec, Vector};
+use rug::Integer;
+
+/**
+
+Sums, intersections and saturations of integer lattices
+
+Every operation is carried out on Hermite normal forms: L₁ + L₂ is spanned
+by the union of both bases, and L₁ ∩ L₂ is the image of the integer
+relations between the rows of B₁ and B₂. The saturation (span L) ∩ Z^m is
+the integer kernel of the integer kernel of B. Results are returned in
+their canonical (HNF) basis, and generating sets are accepted as input.
+
+# Examples
+
+```rust
+use rug::Integer;
+use svp::{Lattice, Vector};
+
+let int = |v: Vec<i64>| Vector::from(&Vector { vec: v, norm: None });
+
+let l1 = Lattice { basis: vec![int(vec![2, 0]), int(vec![0, 3])] };
+let l2 = Lattice { basis: vec![int(vec![4, 0]), int(vec![1, 1])] };
+
+// L₁ + L₂ = Z²
+assert_eq!(l1.sum(&l2).basis[0].vec, vec![1, 0]);
+assert_eq!(l1.sum(&l2).basis[1].vec, vec![0, 1]);
+
+// L₁ ∩ L₂ is a sublattice of both
+let meet = l1.intersection(&l2);
+assert_eq!(l1.index(&meet), Some(Integer::from(4)));
+assert_eq!(l2.index(&meet), Some(Integer::from(6)));
+
+// The saturation of 2Z·(1, 2, 3)
+let l = Lattice { basis: vec![int(vec![2, 4, 6])] };
+let sat = l.saturation();
+assert_eq!(sat.basis[0].vec, vec![1, 2, 3]);
+assert_eq!(sat.index(&l), Some(Integer::from(2)));
+```
+**/
+impl Lattice<Integer> {
+    /// The sum L₁ + L₂ = { u + v : u ∈ L₁, v ∈ L₂ }
+    pub fn sum(&self, other: &Lattice<Integer>) -> Lattice<Integer> {
+        let mut b = self.clone();
+        b.basis.extend(other.basis.iter().cloned());
+        b.canonical()
+    }
+
+    /// The intersection L₁ ∩ L₂
+    pub fn intersection(&self, other: &Lattice<Integer>) -> Lattice<Integer> {
+        let n = self.basis.len();
+        let mut b = self.clone();
+        b.basis.extend(other.basis.iter().cloned());
+
+        // u·B₁ = -u'·B₂ for every relation (u, u') between the rows
+        let basis = relations(&b)
+            .iter()
+            .map(|r| {
+                let u = Vector {
+                    vec: r.vec[..n].to_vec(),
+                    norm: None,
+                };
+                &u * self
+            })
+            .collect();
+        Lattice { basis }.canonical()
+    }
+
+    /// The saturation (span L) ∩ Z^m, the smallest primitive lattice containing L
+    pub fn saturation(&self) -> Lattice<Integer> {
+        if self.basis.is_empty() {
+            return self.clone();
+        }
+        let m = self.basis[0].vec.len();
+        let kernel = relations(&transpose(&self.basis, m));
+        if kernel.is_empty() {
+            return identity(m);
+        }
+        Lattice {
+            basis: relations(&transpose(&kernel, m)),
+        }
+        .canonical()
+    }
+
+    /// The index [L : L'] of a sublattice L' = L(sub) of the same rank, or
+    /// `None` if L' is not a sublattice or has a lower rank
+    pub fn index(&self, sub: &Lattice<Integer>) -> Option<Integer> {
+        let factors = self.quotient(sub)?;
+        match factors.iter().any(|d| *d == 0) {
+            true => None,
+            false => Some(factors.iter().product()),
+        }
+    }
+}
+
+/// A basis of the integer relations u with u·B = 0
+fn relations(b: &Lattice<Integer>) -> Vec<Vector<Integer>> {
+    let hnf = b.hnf();
+    hnf.u[hnf.h.basis.len()..].to_vec()
+}
+
+/// The transpose of an n×m matrix, as a lattice generated by its m columns
+fn transpose(b: &[Vector<Integer>], m: usize) -> Lattice<Integer> {
+    Lattice {
+        basis: (0..m)
+            .map(|j| Vector {
+                vec: b.iter().map(|r| r.vec[j].clone()).collect(),
+                norm: None,
+            })
+            .collect(),
+    }
+}
+
+/// The standard basis of Z^m
+fn identity(m: usize) -> Lattice<Integer> {
+    Lattice {
+        basis: (0..m)
+            .map(|i| {
+                let mut e = nvec![Integer::new(); m];
+                e.vec[i] = Integer::from(1);
+                e
+            })
+            .collect(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::*;
+    use rug::Integer;
+
+    fn lattice(b: Vec<Vec<i64>>) -> Lattice<Integer> {
+        let l = Lattice {
+            basis: b
+                .into_iter()
+                .map(|v| Vector { vec: v, norm: None })
+                .collect(),
+        };
+        Lattice::<Integer>::from(&l)
+    }
+
+    #[test]
+    fn test_sum_intersection() {
+        // 6Z = 2Z ∩ 3Z and Z = 2Z + 3Z
+        let (a, b) = (lattice(vec![vec![2]]), lattice(vec![vec![3]]));
+        assert_eq!(a.intersection(&b).basis[0].vec, vec![6]);
+        assert_eq!(a.sum(&b).basis[0].vec, vec![1]);
+
+        let l1 = lattice(vec![vec![2, 1, 0], vec![0, 3, 1], vec![1, 1, 5]]);
+        let l2 = lattice(vec![vec![4, 0, 2], vec![1, -1, 1], vec![0, 6, 3]]);
+        let (sum, meet) = (l1.sum(&l2), l1.intersection(&l2));
+        assert!(l1.index(&meet).is_some() && l2.index(&meet).is_some());
+        assert!(sum.index(&l1).is_some() && sum.index(&l2).is_some());
+
+        // [L₁ + L₂ : L₁] = [L₂ : L₁ ∩ L₂]
+        assert_eq!(sum.index(&l1), l2.index(&meet));
+        assert_eq!(sum.index(&l2), l1.index(&meet));
+
+        // Lattices in complementary subspaces meet in the origin only
+        let x = lattice(vec![vec![1, 0, 0]]);
+        let yz = lattice(vec![vec![0, 1, 0], vec![0, 0, 1]]);
+        assert!(x.intersection(&yz).basis.is_empty());
+        assert_eq!(x.sum(&yz).basis.len(), 3);
+    }
+
+    #[test]
+    fn test_saturation() {
+        // The checkerboard lattice D2 inside the plane z = 0
+        let l = lattice(vec![vec![1, 1, 0], vec![1, -1, 0]]);
+        let sat = l.saturation();
+        assert_eq!(sat.basis.len(), 2);
+        assert!(sat.same_lattice(&lattice(vec![vec![1, 0, 0], vec![0, 1, 0]])));
+        assert_eq!(sat.index(&l), Some(Integer::from(2)));
+
+        // Full rank lattices saturate to Z^m
+        let l = lattice(vec![vec![2, 1], vec![0, 3]]);
+        assert_eq!(l.saturation().index(&l), Some(Integer::from(6)));
+
+        // Saturations are primitive
+        let l = lattice(vec![
+            vec![3, 6, 9, 12],
+            vec![0, 4, 8, 2],
+            vec![6, 12, 18, 24],
+        ]);
+        let sat = l.saturation();
+        assert_eq!(sat.basis.len(), 2);
+        assert_eq!(sat.saturation().index(&sat), Some(Integer::from(1)));
+
+        // The zero lattice is its own saturation
+        let empty = lattice(vec![]);
+        assert!(empty.saturation().basis.is_empty());
+        assert!(lattice(vec![vec![0, 0]]).saturation().basis.is_empty());
+        assert!(empty.sum(&empty).basis.is_empty());
+    }
+
+    #[test]
+    fn test_index() {
+        let l = lattice(vec![vec![1, 0], vec![0, 1]]);
+        assert_eq!(
+            l.index(&lattice(vec![vec![2, 0], vec![0, 5]])),
+            Some(Integer::from(10))
+        );
+
+        // Lower rank and non-sublattices have no finite index
+        assert_eq!(l.index(&lattice(vec![vec![2, 0]])), None);
+        assert_eq!(lattice(vec![vec![2, 0], vec![0, 2]]).index(&l), None);
+    }
+}
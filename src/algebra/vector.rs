@@ -1,3 +1,4 @@
+use super::backend::{Overflow, Ring, Scalar};
 use rug::{Float, Integer};
 
 /**
@@ -23,8 +24,8 @@ Vector multiplication corresponds to the scalar product
 ```rust
 use svp::{Vector, nvec};
 
-let u = nvec![1, 0, 0];
-let v = nvec![0, 1, 0];
+let u = nvec![1i64, 0, 0];
+let v = nvec![0i64, 1, 0];
 
 assert_eq!(&u * &v, 0);
 ```
@@ -35,7 +36,7 @@ It can be useful to keep track of the squared norm
 use svp::{Vector, nvec};
 
 // Build an integer vector
-let mut u = nvec![1, 0, 0];
+let mut u = nvec![1i64, 0, 0];
 
 // Compute the squared norm (inner product)
 u.norm = Some(&u * &u);
@@ -43,6 +44,25 @@ u.norm = Some(&u * &u);
 assert_eq!(u.norm.unwrap(), 1);
 ```
 
+Vectors can be added, subtracted, negated and scaled.
+Operations that do not preserve the norm reset it to `None`
+
+```rust
+use svp::{Vector, nvec};
+
+let mut u = nvec![1i64, 2, 3];
+let v = nvec![1, 0, -1];
+u.norm = Some(&u * &u);
+
+assert_eq!((&u + &v).vec, vec![2, 2, 2]);
+assert_eq!((&u - &v).vec, vec![0, 2, 4]);
+assert_eq!((-&u).norm, Some(14));
+assert_eq!((&u * 2).norm, Some(56));
+
+u -= &v;
+assert_eq!(u.norm, None);
+```
+
 As a special case, big integers can be used for arbitrary precision
 
 ```rust
@@ -59,43 +79,33 @@ assert_eq!(&v * &v, Integer::from(1));
 **/
 
 #[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(bound = "T: crate::SerdeScalar"))]
 pub struct Vector<T> {
-    pub vec: Vec<T>,     // n-vector
+    #[cfg_attr(feature = "serde", serde(with = "crate::algebra::serialize::entries"))]
+    pub vec: Vec<T>, // n-vector
+    #[cfg_attr(feature = "serde", serde(with = "crate::algebra::serialize::optional"))]
     pub norm: Option<T>, // squared norm
 }
 
 /// `GaussReduce` with respect to v
 pub trait GaussReduce<T> {
+    /// Machine integers wrap on overflow in release builds and panic in
+    /// debug builds, use `try_reduce` where the entries may grow large
     fn reduce(&mut self, v: &Vector<T>) -> bool;
-}
 
-impl std::ops::Mul for &Vector<i64> {
-    /// The resulting scalar type of the inner product
-    type Output = i64;
-    /// Compute the inner product of two n-vectors
-    #[inline]
-    fn mul(self, _rhs: &Vector<i64>) -> i64 {
-        assert!(!self.vec.is_empty() && self.vec.len() == _rhs.vec.len());
-        let mut res = self.vec[0] * _rhs.vec[0];
-        for i in 1..self.vec.len() {
-            res += self.vec[i] * _rhs.vec[i];
-        }
-        res
-    }
+    /// `GaussReduce` with respect to v, failing instead of overflowing
+    fn try_reduce(&mut self, v: &Vector<T>) -> Result<bool, Overflow>;
 }
 
-impl std::ops::Mul for &Vector<f64> {
+impl<T: Scalar> std::ops::Mul for &Vector<T> {
     /// The resulting scalar type of the inner product
-    type Output = f64;
-    /// Compute the inner product of two n-vectors
+    type Output = T;
+    /// Compute the inner product of two n-vectors, wrapping like `T::dot`
     #[inline]
-    fn mul(self, _rhs: &Vector<f64>) -> f64 {
+    fn mul(self, _rhs: &Vector<T>) -> T {
         assert!(!self.vec.is_empty() && self.vec.len() == _rhs.vec.len());
-        let mut res: f64 = self.vec[0] * _rhs.vec[0];
-        for i in 1..self.vec.len() {
-            res += self.vec[i] * _rhs.vec[i];
-        }
-        res
+        T::dot(&self.vec, &_rhs.vec)
     }
 }
 
@@ -114,82 +124,250 @@ impl std::ops::Mul<&Vector<f64>> for &Vector<i64> {
     }
 }
 
-impl std::ops::Mul for &Vector<Integer> {
+impl std::ops::Mul<&Vector<Float>> for &Vector<Integer> {
     /// The resulting scalar type of the inner product
-    type Output = Integer;
+    type Output = Float;
     /// Compute the inner product of two arbitrary precision n-vectors
     #[inline]
-    fn mul(self, _rhs: &Vector<Integer>) -> Integer {
+    fn mul(self, _rhs: &Vector<Float>) -> Float {
         assert!(!self.vec.is_empty() && self.vec.len() == _rhs.vec.len());
-        let mut res: Integer = Integer::from(&self.vec[0] * &_rhs.vec[0]);
+        let prec = _rhs.vec[0].prec();
+        let mut res: Float = Float::with_val(prec, &_rhs.vec[0] * &self.vec[0]);
         for i in 1..self.vec.len() {
-            res += &self.vec[i] * &_rhs.vec[i];
+            res += Float::with_val(prec, &self.vec[i] * &_rhs.vec[i]);
         }
         res
     }
 }
 
-impl std::ops::Mul for &Vector<Float> {
-    /// The resulting scalar type of the inner product
-    type Output = Float;
-    /// Compute the inner product of two arbitrary precision n-vectors
-    #[inline]
-    fn mul(self, _rhs: &Vector<Float>) -> Float {
-        assert!(!self.vec.is_empty() && self.vec.len() == _rhs.vec.len());
-        let mut res: Float = Float::with_val(self.vec[0].prec(), &self.vec[0] * &_rhs.vec[0]);
-        for i in 1..self.vec.len() {
-            res += &self.vec[i] * &_rhs.vec[i];
-        }
+/// Add two n-vectors, the sum's norm is left unset
+impl<T> std::ops::Add for &Vector<T>
+where
+    T: Clone + for<'a> std::ops::AddAssign<&'a T>,
+{
+    type Output = Vector<T>;
+    fn add(self, _rhs: &Vector<T>) -> Vector<T> {
+        let mut res = self.clone();
+        res += _rhs;
         res
     }
 }
 
-impl std::ops::Mul<&Vector<Float>> for &Vector<Integer> {
-    /// The resulting scalar type of the inner product
-    type Output = Float;
-    /// Compute the inner product of two arbitrary precision n-vectors
-    #[inline]
-    fn mul(self, _rhs: &Vector<Float>) -> Float {
-        assert!(!self.vec.is_empty() && self.vec.len() == _rhs.vec.len());
-        let prec = _rhs.vec[0].prec();
-        let mut res: Float = Float::with_val(prec, &_rhs.vec[0] * &self.vec[0]);
-        for i in 1..self.vec.len() {
-            res += Float::with_val(prec, &self.vec[i] * &_rhs.vec[i]);
+/// Subtract two n-vectors, the difference's norm is left unset
+impl<T> std::ops::Sub for &Vector<T>
+where
+    T: Clone + for<'a> std::ops::SubAssign<&'a T>,
+{
+    type Output = Vector<T>;
+    fn sub(self, _rhs: &Vector<T>) -> Vector<T> {
+        let mut res = self.clone();
+        res -= _rhs;
+        res
+    }
+}
+
+/// Negate an n-vector, the norm is preserved
+impl<T> std::ops::Neg for &Vector<T>
+where
+    T: Clone + std::ops::Neg<Output = T>,
+{
+    type Output = Vector<T>;
+    fn neg(self) -> Vector<T> {
+        Vector {
+            vec: self.vec.iter().map(|i| -i.clone()).collect(),
+            norm: self.norm.clone(),
         }
+    }
+}
+
+/// Scale an n-vector, the norm is scaled by the square of the scalar
+impl<T> std::ops::Mul<T> for &Vector<T>
+where
+    T: Clone + for<'a> std::ops::MulAssign<&'a T>,
+{
+    type Output = Vector<T>;
+    fn mul(self, _rhs: T) -> Vector<T> {
+        let mut res = self.clone();
+        res *= _rhs;
         res
     }
 }
 
-impl GaussReduce<i64> for Vector<i64> {
+/// Add two n-vectors, reusing the storage of the left operand
+impl<T> std::ops::Add<&Vector<T>> for Vector<T>
+where
+    T: for<'a> std::ops::AddAssign<&'a T>,
+{
+    type Output = Vector<T>;
+    fn add(mut self, _rhs: &Vector<T>) -> Vector<T> {
+        self += _rhs;
+        self
+    }
+}
+
+impl<T> std::ops::Add for Vector<T>
+where
+    T: for<'a> std::ops::AddAssign<&'a T>,
+{
+    type Output = Vector<T>;
+    fn add(self, _rhs: Vector<T>) -> Vector<T> {
+        self + &_rhs
+    }
+}
+
+impl<T> std::ops::Add<Vector<T>> for &Vector<T>
+where
+    T: Clone + for<'a> std::ops::AddAssign<&'a T>,
+{
+    type Output = Vector<T>;
+    fn add(self, _rhs: Vector<T>) -> Vector<T> {
+        self + &_rhs
+    }
+}
+
+/// Subtract two n-vectors, reusing the storage of the left operand
+impl<T> std::ops::Sub<&Vector<T>> for Vector<T>
+where
+    T: for<'a> std::ops::SubAssign<&'a T>,
+{
+    type Output = Vector<T>;
+    fn sub(mut self, _rhs: &Vector<T>) -> Vector<T> {
+        self -= _rhs;
+        self
+    }
+}
+
+impl<T> std::ops::Sub for Vector<T>
+where
+    T: for<'a> std::ops::SubAssign<&'a T>,
+{
+    type Output = Vector<T>;
+    fn sub(self, _rhs: Vector<T>) -> Vector<T> {
+        self - &_rhs
+    }
+}
+
+impl<T> std::ops::Sub<Vector<T>> for &Vector<T>
+where
+    T: Clone + for<'a> std::ops::SubAssign<&'a T>,
+{
+    type Output = Vector<T>;
+    fn sub(self, _rhs: Vector<T>) -> Vector<T> {
+        self - &_rhs
+    }
+}
+
+impl<T> std::ops::Neg for Vector<T>
+where
+    T: Clone + std::ops::Neg<Output = T>,
+{
+    type Output = Vector<T>;
+    fn neg(self) -> Vector<T> {
+        -&self
+    }
+}
+
+impl<T> std::ops::Mul<T> for Vector<T>
+where
+    T: for<'a> std::ops::MulAssign<&'a T>,
+{
+    type Output = Vector<T>;
+    fn mul(mut self, _rhs: T) -> Vector<T> {
+        self *= _rhs;
+        self
+    }
+}
+
+/// In-place addition, invalidates the norm
+impl<T> std::ops::AddAssign<&Vector<T>> for Vector<T>
+where
+    T: for<'a> std::ops::AddAssign<&'a T>,
+{
+    fn add_assign(&mut self, _rhs: &Vector<T>) {
+        assert_eq!(self.vec.len(), _rhs.vec.len());
+        for (a, b) in self.vec.iter_mut().zip(&_rhs.vec) {
+            *a += b;
+        }
+        self.norm = None;
+    }
+}
+
+/// In-place subtraction, invalidates the norm
+impl<T> std::ops::SubAssign<&Vector<T>> for Vector<T>
+where
+    T: for<'a> std::ops::SubAssign<&'a T>,
+{
+    fn sub_assign(&mut self, _rhs: &Vector<T>) {
+        assert_eq!(self.vec.len(), _rhs.vec.len());
+        for (a, b) in self.vec.iter_mut().zip(&_rhs.vec) {
+            *a -= b;
+        }
+        self.norm = None;
+    }
+}
+
+/// In-place scaling, the norm is scaled by the square of the scalar
+impl<T> std::ops::MulAssign<T> for Vector<T>
+where
+    T: for<'a> std::ops::MulAssign<&'a T>,
+{
+    fn mul_assign(&mut self, _rhs: T) {
+        for a in self.vec.iter_mut() {
+            *a *= &_rhs;
+        }
+        if let Some(norm) = self.norm.as_mut() {
+            *norm *= &_rhs;
+            *norm *= &_rhs;
+        }
+    }
+}
+
+impl<T: Ring> GaussReduce<T> for Vector<T> {
     /// `GaussReduce` with respect to v
-    fn reduce(&mut self, v: &Vector<i64>) -> bool {
+    fn reduce(&mut self, v: &Vector<T>) -> bool {
         let ip = &*self * v;
-        if v.norm.unwrap() < (ip << 1).abs() {
-            let q = (ip as f64 / v.norm.unwrap() as f64).round() as i64;
-            for i in 0..self.vec.len() {
-                self.vec[i] -= q * v.vec[i];
+        let mut ip2 = ip.abs();
+        ip2 += &ip.abs();
+        if v.norm.as_ref().unwrap() < &ip2 {
+            let q = ip.round_div(v.norm.as_ref().unwrap());
+            for (a, b) in self.vec.iter_mut().zip(&v.vec) {
+                a.sub_mul(&q, b);
             }
             self.norm = Some(&*self * &*self);
             return true;
         }
         false
     }
-}
 
-impl GaussReduce<Integer> for Vector<Integer> {
-    /// `GaussReduce` with respect to v
-    fn reduce(&mut self, v: &Vector<Integer>) -> bool {
-        let ip = &*self * v;
-        let ip2: Integer = ip.clone() * 2;
-        if v.norm.as_ref().unwrap() < &ip2.abs() {
-            let (q, _) = ip.div_rem_round(v.norm.clone().unwrap());
+    /// `GaussReduce` with respect to v, failing instead of overflowing
+    fn try_reduce(&mut self, v: &Vector<T>) -> Result<bool, Overflow> {
+        let ip = T::checked_dot(&self.vec, &v.vec)?;
+        let ip_abs = ip.checked_abs()?;
+        if v.norm.as_ref().unwrap() < &ip_abs.checked_add(&ip_abs)? {
+            let q = ip.round_div(v.norm.as_ref().unwrap());
+            let mut res = Vec::with_capacity(self.vec.len());
             for i in 0..self.vec.len() {
-                self.vec[i] -= &q * &v.vec[i];
+                res.push(self.vec[i].checked_sub(&q.checked_mul(&v.vec[i])?)?);
             }
-            self.norm = Some(&*self * &*self);
-            return true;
+            self.norm = Some(T::checked_dot(&res, &res)?);
+            self.vec = res;
+            return Ok(true);
+        }
+        Ok(false)
+    }
+}
+
+/// Widen a machine integer vector to arbitrary precision
+impl<T> From<&Vector<T>> for Vector<Integer>
+where
+    T: Clone,
+    Integer: From<T>,
+{
+    fn from(v: &Vector<T>) -> Self {
+        Vector {
+            vec: v.vec.iter().map(|i| Integer::from(i.clone())).collect(),
+            norm: v.norm.clone().map(Integer::from),
         }
-        false
     }
 }
 
@@ -233,13 +411,13 @@ mod tests {
 
     #[test]
     fn test_prim() {
-        let mut v1 = nvec![1; 5];
+        let mut v1 = nvec![1i64; 5];
         v1.norm = Some(&v1 * &v1);
         assert_eq!(v1.norm.unwrap(), 5);
 
-        let mut e0 = nvec![1, 0, 0];
-        let mut e1 = nvec![0, 1, 0];
-        let mut e2 = nvec![0, 0, 1];
+        let mut e0 = nvec![1i64, 0, 0];
+        let mut e1 = nvec![0i64, 1, 0];
+        let mut e2 = nvec![0i64, 0, 1];
 
         e0.norm = Some(&e0 * &e0);
         e1.norm = Some(&e0 * &e0);
@@ -262,4 +440,89 @@ mod tests {
         e2.norm = Some(&e2 * &e2);
         assert!(e0.norm == e1.norm && e1.norm == e2.norm);
     }
+
+    #[test]
+    fn test_ops() {
+        let mut u = nvec![1i64, 2, 3];
+        let v = nvec![-1, 0, 1];
+        u.norm = Some(&u * &u);
+
+        assert_eq!((&u + &v).vec, vec![0, 2, 4]);
+        assert_eq!((&u - &v).vec, vec![2, 2, 2]);
+        assert_eq!((&u + &v).norm, None);
+
+        let w = -&u;
+        assert_eq!(w.vec, vec![-1, -2, -3]);
+        assert_eq!(w.norm, u.norm);
+
+        let w = &u * 3;
+        assert_eq!(w.norm, Some(&w * &w));
+
+        // Owned operands
+        assert_eq!((u.clone() + &v).vec, vec![0, 2, 4]);
+        assert_eq!((&u + v.clone()).vec, vec![0, 2, 4]);
+        assert_eq!((u.clone() - v.clone()).vec, vec![2, 2, 2]);
+        assert_eq!((-u.clone()).norm, u.norm);
+        let w = u.clone() * 3;
+        assert_eq!(w.norm, Some(&w * &w));
+
+        let mut w = u.clone();
+        w += &v;
+        w -= &v;
+        assert_eq!(w.vec, u.vec);
+        assert_eq!(w.norm, None);
+
+        let mut x = nvec![0.5, -1.5];
+        x.norm = Some(&x * &x);
+        x *= 2.0;
+        assert_eq!(x.vec, vec![1.0, -3.0]);
+        assert_eq!(x.norm, Some(&x * &x));
+    }
+
+    #[test]
+    fn test_ops_mp() {
+        let mut u = nvec![Integer::from(1), Integer::from(2), Integer::from(3)];
+        let v = nvec![Integer::from(-1), Integer::new(), Integer::from(1)];
+        u.norm = Some(&u * &u);
+
+        assert_eq!((&u + &v).vec, vec![0, 2, 4]);
+        assert_eq!((&u - &v).vec, vec![2, 2, 2]);
+        assert_eq!((-&u).norm, u.norm);
+
+        let w = &u * Integer::from(-2);
+        assert_eq!(w.norm, Some(&w * &w));
+        let w = u.clone() * Integer::from(-2);
+        assert_eq!(w.norm, Some(&w * &w));
+        assert_eq!((u.clone() - &v + v).vec, u.vec);
+
+        let mut x = nvec![Float::with_val(64, 0.5), Float::with_val(64, -1.5)];
+        x.norm = Some(&x * &x);
+        x *= Float::with_val(64, 2);
+        assert_eq!(x.vec, vec![1.0, -3.0]);
+        assert_eq!(x.norm, Some(&x * &x));
+
+        x -= &x.clone();
+        assert_eq!(x.vec, vec![0.0, 0.0]);
+        assert_eq!(x.norm, None);
+    }
+
+    #[test]
+    fn test_overflow() {
+        let mut u: Vector<i64> = nvec![1 << 31, 1 << 31];
+        let mut v: Vector<i64> = nvec![1 << 31, 0];
+        v.norm = Some(&v * &v);
+        assert_eq!(u.try_reduce(&v), Err(Overflow));
+
+        let mut w = Vector::<Integer>::from(&u);
+        let v = Vector::<Integer>::from(&v);
+        assert!(w.reduce(&v));
+        assert_eq!(w.vec, vec![0, 1i64 << 31]);
+
+        u.vec = vec![3, 1];
+        let mut v: Vector<i64> = nvec![1, 0];
+        v.norm = Some(1);
+        assert_eq!(u.try_reduce(&v), Ok(true));
+        assert_eq!(u.vec, vec![0, 1]);
+        assert_eq!(u.norm, Some(1));
+    }
 }
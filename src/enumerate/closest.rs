@@ -0,0 +1,193 @@
+use super::schnorr_euchner::{leaf, with_slack, Tree};
+use crate::{nvec, Field, Lattice, Ring, Scalar, Vector};
+
+/**
+
+Solves the closest vector problem by Schnorr-Euchner enumeration \[SE94\]
+
+The target t is written as Σ τ_i b*_i plus a component orthogonal to the
+span of B, and the enumeration tree is centered at τ instead of the origin.
+The search starts from Babai's nearest plane solution, and candidates are
+compared by their exact squared distances ‖t - v‖². The basis vectors must
+be linearly independent, and as for `Enumerate` the basis should be reduced
+beforehand.
+
+# Examples
+
+```rust
+use svp::{nvec, Closest, Lattice, Vector};
+
+let l = Lattice {
+    basis: vec![nvec![1i64, 1, 0], nvec![1, 2, 0], nvec![0, 1, 2]],
+};
+
+// (0, 0, 1) lies halfway between (0, 0, 0) and (0, 0, 2) = b_2 + b_0 - b_1
+let v = l.closest(&nvec![0, 0, 1]);
+assert_eq!(v.vec[0..2], [0, 0]);
+
+// The distance of a target outside of the span
+let t = nvec![3, 5, 7];
+let v = l.closest(&t);
+let d = &t - &v;
+assert_eq!(&d * &d, 1);
+```
+**/
+pub trait Closest<T> {
+    /// Returns a lattice vector closest to t, along with its squared norm
+    ///
+    /// The empty basis spans only the zero vector, which is returned with
+    /// the length of t.
+    fn closest(&self, t: &Vector<T>) -> Vector<T>;
+}
+
+/// The exact squared distance ‖t - v‖²
+fn distance<T: Ring>(t: &Vector<T>, v: &Vector<T>) -> T {
+    let d = t - v;
+    T::dot(&d.vec, &d.vec)
+}
+
+impl<T: Ring> Closest<T> for Lattice<T> {
+    fn closest(&self, t: &Vector<T>) -> Vector<T> {
+        if self.basis.is_empty() {
+            let mut v = nvec![T::zero(); t.vec.len()];
+            v.norm = Some(T::zero());
+            return v;
+        }
+        let mut rows = self.basis.clone();
+        rows.push(t.clone());
+        let prec = Lattice { basis: rows }
+            .estimate_precision()
+            .max(T::PRECISION);
+        let (mu, r) = self.gram().gso_coef(prec);
+        let n = r.len();
+
+        // τ_k = <t, b*_k> / ‖b*_k‖², where
+        // <t, b*_k> = <t, b_k> - Σ_{j < k} μ_{k,j} <t, b*_j>
+        let tf: Vec<T::Field> = t.vec.iter().map(|x| x.to_field(prec)).collect();
+        let mut tb: Vec<T::Field> = vec![];
+        for k in 0..n {
+            let b: Vec<T::Field> = self.basis[k].vec.iter().map(|x| x.to_field(prec)).collect();
+            let mut x = T::Field::dot(&tf, &b);
+            for j in 0..k {
+                let mut tmp = mu[k][j].clone();
+                tmp *= &tb[j];
+                x -= &tmp;
+            }
+            tb.push(x);
+        }
+        let center: Vec<T::Field> = tb
+            .into_iter()
+            .zip(&r)
+            .map(|(mut x, rk)| {
+                x /= rk;
+                x
+            })
+            .collect();
+
+        // Babai's nearest plane bounds the search radius
+        let mut x = vec![T::Field::zero(prec); n];
+        for k in (0..n).rev() {
+            let mut c = center[k].clone();
+            for j in k + 1..n {
+                let mut tmp = x[j].clone();
+                tmp *= &mu[j][k];
+                c -= &tmp;
+            }
+            x[k] = c.round();
+        }
+        let point = |x: &[T::Field]| &leaf::<T>(x) * self;
+        let mut best = point(&x);
+        let mut best_dist = distance(t, &best);
+
+        // Keep a leaf if it is strictly closer than the best so far
+        let mut tree = Tree::new(mu, r, with_slack(&best_dist, prec)).with_center(center);
+        tree.run(&mut |x, _| {
+            let v = point(x);
+            let dist = distance(t, &v);
+            if dist >= best_dist {
+                return None;
+            }
+            let radius = with_slack(&dist, prec);
+            best = v;
+            best_dist = dist;
+            Some(radius)
+        });
+        best
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::testing;
+    use crate::*;
+    use rug::Integer;
+
+    /// The closest vector by brute force over small coefficient vectors
+    fn brute_force(l: &Lattice<i64>, t: &Vector<i64>, bound: i64) -> i64 {
+        let n = l.basis.len();
+        let mut z = nvec![-bound; n];
+        let mut best = i64::MAX;
+        loop {
+            let d = t - &(&z * l);
+            best = best.min(&d * &d);
+            let mut i = 0;
+            while i < n && z.vec[i] == bound {
+                z.vec[i] = -bound;
+                i += 1;
+            }
+            if i == n {
+                return best;
+            }
+            z.vec[i] += 1;
+        }
+    }
+
+    #[test]
+    fn test_prim() {
+        let mut l = testing::dim10();
+        l.lll(0.99);
+        let sub = Lattice {
+            basis: l.basis[..4].to_vec(),
+        };
+
+        // Lattice points are their own closest vectors
+        let v = &nvec![2, -1, 0, 3, 1, 0, 0, -2, 1, 1] * &l;
+        assert_eq!(l.closest(&v).vec, v.vec);
+
+        // Against brute force in a sublattice
+        for t in [
+            nvec![3, -7, 2, 5, 0, 1, -4, 9, 2, -3],
+            nvec![10, 10, 10, 10, 10, 10, 10, 10, 10, 10],
+        ] {
+            let w = sub.closest(&t);
+            let d = &t - &w;
+            assert_eq!(&d * &d, brute_force(&sub, &t, 4));
+            assert_eq!(w.norm, Some(&w * &w));
+        }
+    }
+
+    #[test]
+    fn test_mp() {
+        let l = testing::knapsack4();
+        let v = &nvec![
+            Integer::from(7),
+            Integer::from(-3),
+            Integer::from(11),
+            Integer::from(-5)
+        ] * &l;
+
+        // A perturbed lattice point, closer to v than half of λ₁(L) = √39
+        let mut t = v.clone();
+        t.vec[0] += 1;
+        t.vec[3] -= 2;
+        assert_eq!(l.closest(&t).vec, v.vec);
+    }
+
+    #[test]
+    fn test_empty() {
+        let l: Lattice<i64> = Lattice { basis: vec![] };
+        let v = l.closest(&nvec![3, -1, 2]);
+        assert_eq!(v.vec, vec![0, 0, 0]);
+        assert_eq!(v.norm, Some(0));
+    }
+}
@@ -0,0 +1,226 @@
+use super::schnorr_euchner::{leaf, with_slack, Tree};
+use crate::{GramLattice, Lattice, Ring, Vector, LLL};
+use rug::{Integer, Rational};
+
+/**
+
+Computes the successive minima λ₁ ≤ .. ≤ λₙ of a lattice with witnesses
+
+λ_i is the least radius containing i linearly independent lattice vectors.
+Since the basis vectors themselves are independent, λₙ² ≤ max ‖b_i‖², so
+every vector within that radius is enumerated, and witnesses are picked
+greedily by increasing exact norm, testing linear independence over the
+rationals. Lattices given by a basis are LLL-reduced first, Gram matrices
+are enumerated as given. The number of candidates grows exponentially with
+the dimension, so this is only practical in small dimensions.
+
+# Examples
+
+```rust
+use svp::{nvec, GramLattice, Lattice, SuccessiveMinima, Vector};
+
+let l = Lattice {
+    basis: vec![nvec![1i64, 0, 0], nvec![7, 2, 0], nvec![3, 5, 3]],
+};
+let m = l.successive_minima();
+let norms: Vec<i64> = m.iter().map(|v| v.norm.unwrap()).collect();
+assert_eq!(norms, vec![1, 4, 10]);
+
+// The hexagonal lattice A2 has λ₁ = λ₂
+let g = GramLattice::new(vec![nvec![2i64, -1], nvec![-1, 2]]);
+let m = g.successive_minima();
+assert_eq!((m[0].norm, m[1].norm), (Some(2), Some(2)));
+```
+**/
+pub trait SuccessiveMinima<T> {
+    /// Linearly independent v_1, .., v_n with ‖v_i‖ = λ_i, along with their
+    /// squared norms
+    fn successive_minima(&self) -> Vec<Vector<T>>;
+}
+
+/// Incremental row echelon form over the rationals
+struct Echelon {
+    rows: Vec<(usize, Vec<Rational>)>, // pivot column and row
+}
+
+impl Echelon {
+    /// Add v to the span, unless it is already in it
+    fn insert(&mut self, v: Vec<Rational>) -> bool {
+        let mut v = v;
+        for (p, row) in &self.rows {
+            if v[*p] == 0 {
+                continue;
+            }
+            let f = Rational::from(&v[*p] / &row[*p]);
+            for (x, y) in v.iter_mut().zip(row) {
+                *x -= Rational::from(&f * y);
+            }
+        }
+        match v.iter().position(|x| *x != 0) {
+            Some(p) => {
+                self.rows.push((p, v));
+                true
+            }
+            None => false,
+        }
+    }
+}
+
+impl<T> SuccessiveMinima<T> for GramLattice<T>
+where
+    T: Ring,
+    Integer: From<T>,
+{
+    fn successive_minima(&self) -> Vec<Vector<T>> {
+        let n = self.dim();
+        if n == 0 {
+            return vec![];
+        }
+        let prec = self.estimate_precision().max(T::PRECISION);
+        let (mu, r) = self.gso_coef(prec);
+        let bound = (0..n)
+            .map(|i| self.gram[i].vec[i].clone())
+            .reduce(|a, b| if b > a { b } else { a })
+            .unwrap();
+
+        // Every vector within the radius, up to sign
+        let mut candidates: Vec<Vector<T>> = vec![];
+        let mut tree = Tree::new(mu, r, with_slack(&bound, prec));
+        tree.run(&mut |x, _| {
+            let mut z: Vector<T> = leaf(x);
+            let norm = self.norm(&z);
+            if !norm.is_zero() && norm <= bound {
+                z.norm = Some(norm);
+                candidates.push(z);
+            }
+            None
+        });
+        candidates.sort_by(|a, b| a.norm.partial_cmp(&b.norm).unwrap());
+
+        let mut echelon = Echelon { rows: vec![] };
+        let mut minima = vec![];
+        for z in candidates {
+            let v = z
+                .vec
+                .iter()
+                .map(|x| Rational::from(Integer::from(x.clone())))
+                .collect();
+            if echelon.insert(v) {
+                minima.push(z);
+                if minima.len() == n {
+                    break;
+                }
+            }
+        }
+        minima
+    }
+}
+
+impl<T> SuccessiveMinima<T> for Lattice<T>
+where
+    T: Ring,
+    Integer: From<T>,
+{
+    fn successive_minima(&self) -> Vec<Vector<T>> {
+        let mut l = self.clone();
+        l.lll(0.99);
+        l.gram()
+            .successive_minima()
+            .iter()
+            .map(|z| z * &l)
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::testing;
+    use crate::*;
+    use rug::Integer;
+
+    #[test]
+    fn test_prim() {
+        let l = testing::dim10();
+        let m = l.successive_minima();
+        assert_eq!(m.len(), 10);
+        assert_eq!(m[0].norm, l.shortest().norm);
+
+        // Independent lattice vectors with non-decreasing norms, λₙ bounded
+        // by the longest vector of any basis
+        let w = Lattice { basis: m.clone() };
+        assert_eq!(w.rank(), 10);
+        assert!(l.coordinates_batch(&m).iter().all(|z| z.is_some()));
+        for v in &m {
+            assert_eq!(v.norm, Some(v * v));
+        }
+        assert!(m.windows(2).all(|w| w[0].norm <= w[1].norm));
+        let mut h = l.clone();
+        h.hkz();
+        assert!(m[9].norm <= h.basis.iter().map(|v| v.norm).max().unwrap());
+    }
+
+    #[test]
+    fn test_e8() {
+        // Every successive minimum of E8 is √2
+        let e8: Vec<Vector<i64>> = vec![
+            nvec![2, -1, 0, 0, 0, 0, 0, 0],
+            nvec![-1, 2, -1, 0, 0, 0, 0, 0],
+            nvec![0, -1, 2, -1, 0, 0, 0, 0],
+            nvec![0, 0, -1, 2, -1, 0, 0, 0],
+            nvec![0, 0, 0, -1, 2, -1, 0, -1],
+            nvec![0, 0, 0, 0, -1, 2, -1, 0],
+            nvec![0, 0, 0, 0, 0, -1, 2, 0],
+            nvec![0, 0, 0, 0, -1, 0, 0, 2],
+        ];
+        let g = GramLattice::new(e8);
+        let m = g.successive_minima();
+        assert_eq!(m.len(), 8);
+        assert!(m.iter().all(|z| z.norm == Some(2) && g.norm(z) == 2));
+        assert_eq!(Lattice { basis: m }.rank(), 8);
+    }
+
+    #[test]
+    fn test_mp() {
+        // The checkerboard lattice D4, with 24 vectors of minimal norm 2
+        let l = Lattice {
+            basis: vec![
+                nvec![
+                    Integer::from(1),
+                    Integer::from(1),
+                    Integer::new(),
+                    Integer::new()
+                ],
+                nvec![
+                    Integer::new(),
+                    Integer::from(1),
+                    Integer::from(-1),
+                    Integer::new()
+                ],
+                nvec![
+                    Integer::new(),
+                    Integer::new(),
+                    Integer::from(1),
+                    Integer::from(-1)
+                ],
+                nvec![
+                    Integer::new(),
+                    Integer::new(),
+                    Integer::from(1),
+                    Integer::from(1)
+                ],
+            ],
+        };
+        let m = l.successive_minima();
+        assert!(m.iter().all(|v| v.norm.as_ref().unwrap() == &2));
+
+        // A generating set with a dependent vector
+        let mut g = l.clone();
+        g.basis.push(nvec![
+            Integer::from(1),
+            Integer::new(),
+            Integer::from(1),
+            Integer::new()
+        ]);
+        assert_eq!(g.successive_minima().len(), 4);
+    }
+}
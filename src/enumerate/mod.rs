@@ -0,0 +1,8 @@
+mod closest;
+mod minima;
+mod schnorr_euchner;
+
+pub use closest::Closest;
+pub use minima::SuccessiveMinima;
+pub use schnorr_euchner::Enumerate;
+pub(crate) use schnorr_euchner::Tree;
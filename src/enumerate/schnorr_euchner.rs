@@ -0,0 +1,317 @@
+use crate::{Field, GramLattice, Lattice, Ring, Vector, LLL};
+
+/**
+
+Implements Schnorr-Euchner enumeration of a shortest nonzero vector \[SE94\]
+
+The search runs over integer coefficient vectors using only the Gram-Schmidt
+coefficients μ_{i,j} and norms ‖b*_i‖² of the Gram matrix, so lattices given
+by an explicit basis and by a Gram matrix are handled alike. Candidates are
+compared by their exact squared norms, the floating GSO only prunes the tree.
+The running time grows quickly with the skew of the GSO, so the basis should
+be reduced beforehand. Lattices given by a basis are LLL-reduced first, which
+also removes linearly dependent and zero vectors, while Gram matrices must be
+positive definite.
+
+# Examples
+
+```rust
+use svp::{nvec, Enumerate, GramLattice, Lattice, Vector};
+
+let l = Lattice {
+    basis: vec![nvec![1i64, 1, 0], nvec![1, 2, 0], nvec![0, 1, 2]],
+};
+
+// A shortest vector of L(B), b_1 - b_0 up to sign
+let v = l.shortest();
+assert_eq!(v.norm, Some(1));
+
+// The same search in the coefficient space of the Gram matrix
+let z = l.gram().shortest();
+assert_eq!(z.norm, Some(1));
+assert_eq!((&z * &l).norm, Some(1));
+```
+**/
+pub trait Enumerate<T> {
+    /// Returns a shortest nonzero vector along with its squared norm
+    ///
+    /// Panics on an empty basis, whose lattice has no nonzero vector, and on
+    /// a Gram matrix that is not positive definite
+    fn shortest(&self) -> Vector<T>;
+}
+
+/// Depth-first search tree over the coefficient vectors of a lattice given
+/// by its Gram-Schmidt coefficients μ_{i,j} and norms r_i = ‖b*_i‖²
+pub(crate) struct Tree<F> {
+    mu: Vec<Vec<F>>,
+    r: Vec<F>,
+    x: Vec<F>,
+    center: Option<Vec<F>>, // coordinates τ_i of a target Σ τ_i b*_i
+    pub radius: F,          // squared search radius
+    prec: u32,
+}
+
+impl<F: Field> Tree<F> {
+    pub fn new(mu: Vec<Vec<F>>, r: Vec<F>, radius: F) -> Self {
+        let prec = radius.prec();
+        Self {
+            x: vec![F::zero(prec); r.len()],
+            mu,
+            r,
+            center: None,
+            radius,
+            prec,
+        }
+    }
+
+    /// Search around the target Σ τ_i b*_i instead of the origin
+    pub fn with_center(mut self, center: Vec<F>) -> Self {
+        self.center = Some(center);
+        self
+    }
+
+    /// Visit every nonzero x, up to sign, with ‖Σ x_i b_i‖² ≤ radius, or
+    /// with a center every x with ‖Σ (x_i b_i - τ_i b*_i)‖² ≤ radius
+    ///
+    /// `leaf` receives x along with its floating squared distance and may
+    /// return a smaller radius to prune the rest of the search.
+    pub fn run(&mut self, leaf: &mut dyn FnMut(&[F], &F) -> Option<F>) {
+        if self.x.is_empty() {
+            return;
+        }
+        let zero = F::zero(self.prec);
+        let top = self.center.is_none();
+        self.search(self.x.len() - 1, &zero, top, leaf);
+    }
+
+    /// Enumerate x_k given x_{k+1}, .., x_{n-1}
+    ///
+    /// `partial` is the squared length of the projection onto b*_{k+1}, ..,
+    /// b*_{n-1}, and `top` is set while all of those coefficients are zero,
+    /// in which case only x_k ≥ 0 is enumerated to skip ±v pairs.
+    fn search(
+        &mut self,
+        k: usize,
+        partial: &F,
+        top: bool,
+        leaf: &mut dyn FnMut(&[F], &F) -> Option<F>,
+    ) {
+        let zero = F::zero(self.prec);
+        let mut c = match &self.center {
+            Some(t) => t[k].clone(),
+            None => zero.clone(),
+        };
+        for j in k + 1..self.x.len() {
+            let mut tmp = self.x[j].clone();
+            tmp *= &self.mu[j][k];
+            c -= &tmp;
+        }
+        let x0 = c.round();
+
+        // Zig-zag around the center: x0, x0 + 1, x0 - 1, x0 + 2, ..
+        let (mut up, mut down) = (true, !top);
+        let mut d = 0;
+        while up || down {
+            for side in [1.0, -1.0] {
+                match side > 0.0 {
+                    true if !up => continue,
+                    false if !down || d == 0 => continue,
+                    _ => {}
+                }
+
+                let mut xk = F::from_f64(side * d as f64, self.prec);
+                xk += &x0;
+                let mut dist = xk.clone();
+                dist -= &c;
+                let tmp = dist.clone();
+                dist *= &tmp;
+                dist *= &self.r[k];
+                dist += partial;
+
+                if dist > self.radius {
+                    match side > 0.0 {
+                        true => up = false,
+                        false => down = false,
+                    }
+                    // x0 is the closest integer to the center
+                    if d == 0 {
+                        down = false;
+                    }
+                    continue;
+                }
+
+                let top = top && xk == zero;
+                self.x[k] = xk;
+                match k {
+                    0 if !top => {
+                        if let Some(radius) = leaf(&self.x, &dist) {
+                            self.radius = radius;
+                        }
+                    }
+                    0 => {}
+                    _ => self.search(k - 1, &dist, top, leaf),
+                }
+            }
+            d += 1;
+        }
+        self.x[k] = zero;
+    }
+}
+
+/// Widen an exact squared norm so that rounding errors cannot prune it
+pub(super) fn with_slack<T: Ring>(norm: &T, prec: u32) -> T::Field {
+    let mut radius = norm.to_field(prec);
+    let mut eps = radius.clone();
+    eps *= &T::Field::from_f64(2f64.powi(-(prec as i32) / 2), prec);
+    radius += &eps;
+    radius
+}
+
+/// The integer coefficient vector of a leaf, whose entries are bounded by
+/// the search radius
+pub(super) fn leaf<T: Ring>(x: &[T::Field]) -> Vector<T> {
+    Vector {
+        vec: x
+            .iter()
+            .map(T::from_field)
+            .collect::<Result<_, _>>()
+            .expect("machine integer overflow"),
+        norm: None,
+    }
+}
+
+impl<T: Ring> Enumerate<T> for GramLattice<T> {
+    fn shortest(&self) -> Vector<T> {
+        assert!(self.dim() > 0, "the empty lattice has no nonzero vector");
+        let prec = self.estimate_precision().max(T::PRECISION);
+        let (mu, r) = self.gso_coef(prec);
+        // A vanishing b*_k leaves the coefficient x_k unbounded
+        let zero = T::Field::zero(prec);
+        assert!(
+            r.iter().all(|x| x > &zero),
+            "Gram matrix is not positive definite"
+        );
+        let mut best = self.unit(0);
+        let mut tree = Tree::new(mu, r, with_slack(best.norm.as_ref().unwrap(), prec));
+
+        // Keep a leaf if it is strictly shorter than the best so far
+        tree.run(&mut |x, _| {
+            let mut z = leaf(x);
+            let norm = self.norm(&z);
+            if norm.is_zero() || &norm >= best.norm.as_ref().unwrap() {
+                return None;
+            }
+            let radius = with_slack(&norm, prec);
+            z.norm = Some(norm);
+            best = z;
+            Some(radius)
+        });
+        best
+    }
+}
+
+impl<T: Ring> Enumerate<T> for Lattice<T> {
+    fn shortest(&self) -> Vector<T> {
+        let mut l = self.clone();
+        l.lll(0.99);
+        &l.gram().shortest() * &l
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::*;
+    use rand::SeedableRng;
+    use rand_chacha::ChaCha20Rng;
+    use rug::Integer;
+
+    #[test]
+    fn test_prim() {
+        // The Cartan matrix of E8, with minimum 2
+        let e8: Vec<Vector<i64>> = vec![
+            nvec![2, -1, 0, 0, 0, 0, 0, 0],
+            nvec![-1, 2, -1, 0, 0, 0, 0, 0],
+            nvec![0, -1, 2, -1, 0, 0, 0, 0],
+            nvec![0, 0, -1, 2, -1, 0, 0, 0],
+            nvec![0, 0, 0, -1, 2, -1, 0, -1],
+            nvec![0, 0, 0, 0, -1, 2, -1, 0],
+            nvec![0, 0, 0, 0, 0, -1, 2, 0],
+            nvec![0, 0, 0, 0, -1, 0, 0, 2],
+        ];
+        let g = GramLattice::new(e8);
+        let z = g.shortest();
+        assert_eq!(z.norm, Some(2));
+        assert_eq!(g.norm(&z), 2);
+    }
+
+    #[test]
+    fn test_dim10() {
+        let mut rng = ChaCha20Rng::seed_from_u64(10);
+        let q = Integer::from(31);
+        let r = Lattice::random_q_ary(5, 10, &q, &mut rng);
+        let l: Lattice<i64> = r.random_unimodular(&mut rng).narrow().unwrap();
+        let v = l.shortest();
+        assert_eq!(v.norm, Some(30));
+        assert_eq!(r.shortest().norm, v.norm.map(Integer::from));
+
+        // The basis in reverse order spans the same lattice
+        let r = Lattice {
+            basis: l.basis.iter().rev().cloned().collect(),
+        };
+        assert_eq!(r.gram().shortest().norm, v.norm);
+    }
+
+    #[test]
+    fn test_mp() {
+        let l = Lattice {
+            basis: vec![
+                nvec![Integer::from(1), Integer::from(-1), Integer::from(1)],
+                nvec![Integer::from(1), Integer::new(), Integer::from(1)],
+                nvec![Integer::from(1), Integer::from(1), Integer::from(2)],
+            ],
+        };
+        let v = l.shortest();
+        assert_eq!(v.norm.as_ref().unwrap(), &1);
+
+        let g = GramLattice::new(vec![
+            nvec![Integer::from(4), Integer::from(2), Integer::from(2)],
+            nvec![Integer::from(2), Integer::from(4), Integer::from(2)],
+            nvec![Integer::from(2), Integer::from(2), Integer::from(5)],
+        ]);
+        let z = g.shortest();
+        assert_eq!(z.norm.as_ref().unwrap(), &4);
+        assert_eq!(g.norm(&z), 4);
+    }
+
+    #[test]
+    fn test_dependent() {
+        let l = Lattice {
+            basis: vec![nvec![1i64, 2], nvec![2, 4], nvec![0, 1]],
+        };
+        assert_eq!(l.shortest().norm, Some(1));
+
+        // A zero vector is not a candidate
+        let l = Lattice {
+            basis: vec![nvec![0i64, 0, 0], nvec![1, 1, 0], nvec![0, 1, 2]],
+        };
+        let v = l.shortest();
+        assert_eq!(v.norm, Some(2));
+        assert_eq!(v.norm, Some(&v * &v));
+    }
+
+    #[test]
+    #[should_panic(expected = "not positive definite")]
+    fn test_semidefinite() {
+        let l = Lattice {
+            basis: vec![nvec![1i64, 2], nvec![2, 4], nvec![0, 1]],
+        };
+        l.gram().shortest();
+    }
+
+    #[test]
+    #[should_panic(expected = "no nonzero vector")]
+    fn test_empty() {
+        let l: Lattice<i64> = Lattice { basis: vec![] };
+        l.shortest();
+    }
+}
@@ -0,0 +1,162 @@
+use super::parser::{build, Cursor, ParseError, Token};
+use crate::{Lattice, Ring};
+use std::fmt::Display;
+use std::str::FromStr;
+
+/**
+
+Read and write bases in the `[[a b c] [d e f]]` format of fplll and NTL
+
+Rows are bracketed lists of whitespace separated integers, enclosed in one
+more pair of brackets, and line breaks are insignificant. Bases are written
+with one row per line, as fplll and NTL print them.
+
+# Examples
+
+```rust
+use rug::Integer;
+use svp::{Fplll, Lattice};
+
+let l = Lattice::<i64>::from_fplll("[[1 0 1345]\n[0 1 35]\n[0 0 2048]\n]").unwrap();
+assert_eq!(l.basis[2].vec, vec![0, 0, 2048]);
+assert_eq!(l.to_fplll(), "[[1 0 1345]\n[0 1 35]\n[0 0 2048]\n]\n");
+
+// Entries beyond 64 bits need arbitrary precision
+let big = "[[1 36893488147419103232]]";
+assert!(Lattice::<i64>::from_fplll(big).is_err());
+let l = Lattice::<Integer>::from_fplll(big).unwrap();
+assert_eq!(l.basis[0].vec[1], Integer::from(1) << 65);
+
+// Errors point at the offending entry
+let err = Lattice::<i64>::from_fplll("[[1 2]\n [3 x]]").unwrap_err();
+assert_eq!((err.line, err.column), (2, 5));
+assert_eq!(err.to_string(), "line 2, column 5: expected an integer but found `x`");
+```
+**/
+pub trait Fplll: Sized {
+    /// Parse a basis in fplll/NTL format
+    fn from_fplll(s: &str) -> Result<Self, ParseError>;
+
+    /// Write the basis in fplll/NTL format
+    fn to_fplll(&self) -> String;
+}
+
+impl<T> Fplll for Lattice<T>
+where
+    T: Ring + FromStr + Display,
+{
+    fn from_fplll(s: &str) -> Result<Self, ParseError> {
+        let mut c = Cursor::new(s)?;
+        c.expect(Token::Open, "`[`")?;
+        let mut rows = vec![];
+        while *c.peek() != Token::Close {
+            if *c.peek() != Token::Open {
+                return Err(c.unexpected("`[` or `]`"));
+            }
+            rows.push(c.row(false)?);
+        }
+        c.next();
+        c.expect(Token::End, "end of input")?;
+        build(rows, &c)
+    }
+
+    fn to_fplll(&self) -> String {
+        let mut s = String::from("[");
+        for v in &self.basis {
+            let row: Vec<String> = v.vec.iter().map(|x| x.to_string()).collect();
+            s += &format!("[{}]\n", row.join(" "));
+        }
+        s + "]\n"
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::*;
+    use rug::Integer;
+
+    #[test]
+    fn test_prim() {
+        let s = "[[-1 0 1 0 1 0 0 0 -1 1]
+[-2 2 -1 0 2 3 0 1 0 -2]
+[-3 1 -1 1 0 -4 -1 -2 0 0]
+[1 6 0 0 1 0 2 0 0 2]
+[-2 1 -4 -1 -1 0 0 4 -3 2]
+[1 0 -5 -10 4 -3 -2 0 3 4]
+[5 0 -4 4 6 -6 0 4 -9 -7]
+[4 3 -2 -7 -2 3 0 -6 -12 -2]
+[1 6 0 1 -3 3 -15 3 -1 2]
+[0 3 11 -9 -5 -4 -3 8 -1 -7]
+]
+";
+        let l = Lattice::<i64>::from_fplll(s).unwrap();
+        assert_eq!(l.basis.len(), 10);
+        assert_eq!(l.basis[9].vec[2], 11);
+        assert_eq!(l.basis[0].norm, Some(5));
+        assert_eq!(l.to_fplll(), s);
+
+        // Whitespace is insignificant, also fplll's trailing spaces
+        let l = Lattice::<i64>::from_fplll("  [ [1 2 ]\t[3   4 ] ]  ").unwrap();
+        assert_eq!(l.to_fplll(), "[[1 2]\n[3 4]\n]\n");
+    }
+
+    #[test]
+    fn test_errors() {
+        let kind = |s: &str| Lattice::<i64>::from_fplll(s).unwrap_err().kind;
+        assert_eq!(kind("[]"), ParseErrorKind::Empty);
+        assert_eq!(kind("[[]]"), ParseErrorKind::Empty);
+        assert_eq!(
+            kind("[[1.5 2]]"),
+            ParseErrorKind::InvalidEntry("1.5".into())
+        );
+        assert_eq!(
+            kind("[[1 2]\n[3]]"),
+            ParseErrorKind::RowLength {
+                row: 1,
+                expected: 2,
+                found: 1
+            }
+        );
+        assert_eq!(
+            kind("[[1 99999999999999999999]]"),
+            ParseErrorKind::OutOfRange("99999999999999999999".into())
+        );
+
+        // Every entry fits an i64, but ‖b_1‖² = 2·3037000500² does not
+        let err = Lattice::<i64>::from_fplll("[[1 0]\n[3037000500 3037000500]]").unwrap_err();
+        assert_eq!(err.kind, ParseErrorKind::OutOfRange("‖b_1‖²".into()));
+        assert_eq!((err.line, err.column), (2, 1));
+        assert!(matches!(
+            kind("[[1, 2]]"),
+            ParseErrorKind::Unexpected { .. }
+        ));
+        assert!(matches!(kind("[[1 2]"), ParseErrorKind::Unexpected { .. }));
+        assert!(matches!(
+            kind("[[1 2]] 3"),
+            ParseErrorKind::Unexpected { .. }
+        ));
+
+        let err = Lattice::<i64>::from_fplll("[[1 2]\n[3 4]").unwrap_err();
+        assert_eq!((err.line, err.column), (2, 6));
+        assert_eq!(
+            err.to_string(),
+            "line 2, column 6: expected `[` or `]` but found end of input"
+        );
+    }
+
+    #[test]
+    fn test_mp() {
+        let big: Integer = Integer::from(1) << 200;
+        let l = Lattice {
+            basis: vec![
+                nvec![big.clone() + 1, Integer::from(-3)],
+                nvec![Integer::new(), -big.clone()],
+            ],
+        };
+        let s = l.to_fplll();
+        let r = Lattice::<Integer>::from_fplll(&s).unwrap();
+        assert_eq!(r.basis[0].vec, l.basis[0].vec);
+        assert_eq!(r.basis[1].vec, l.basis[1].vec);
+        assert_eq!(r.to_fplll(), s);
+    }
+}
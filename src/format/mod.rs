@@ -0,0 +1,7 @@
+mod fplll;
+mod parser;
+mod sage;
+
+pub use fplll::Fplll;
+pub use parser::{ParseError, ParseErrorKind};
+pub use sage::Sage;
@@ -0,0 +1,283 @@
+use crate::{Lattice, Ring, Vector};
+use std::fmt;
+use std::str::FromStr;
+
+/// A malformed matrix, along with the position where parsing stopped
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParseError {
+    pub line: usize,   // 1-based
+    pub column: usize, // 1-based, in characters
+    pub kind: ParseErrorKind,
+}
+
+/// The reason a matrix could not be parsed
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ParseErrorKind {
+    /// Some token was expected, but another one or the end of input was found
+    Unexpected {
+        expected: &'static str,
+        found: String,
+    },
+    /// An entry that is not an integer, e.g. `1.5` or `2/3`
+    InvalidEntry(String),
+    /// An integer, or the squared norm of a row, that does not fit the entry type
+    OutOfRange(String),
+    /// A row whose length differs from the first row
+    RowLength {
+        row: usize,
+        expected: usize,
+        found: usize,
+    },
+    /// A flat list of entries that does not fill an n×m matrix
+    Dimensions {
+        rows: usize,
+        cols: usize,
+        entries: usize,
+    },
+    /// A base ring other than ZZ
+    UnsupportedRing(String),
+    /// A matrix without rows or columns
+    Empty,
+}
+
+impl fmt::Display for ParseErrorKind {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Self::Unexpected { expected, found } => {
+                write!(f, "expected {} but found {}", expected, found)
+            }
+            Self::InvalidEntry(s) => write!(f, "`{}` is not an integer", s),
+            Self::OutOfRange(s) => write!(f, "`{}` is out of range for the entry type", s),
+            Self::RowLength {
+                row,
+                expected,
+                found,
+            } => write!(
+                f,
+                "row {} has {} entries but the first row has {}",
+                row, found, expected
+            ),
+            Self::Dimensions {
+                rows,
+                cols,
+                entries,
+            } => write!(
+                f,
+                "{} entries do not fill a {}x{} matrix",
+                entries, rows, cols
+            ),
+            Self::UnsupportedRing(s) => write!(f, "unsupported base ring `{}`, expected ZZ", s),
+            Self::Empty => write!(f, "the matrix is empty"),
+        }
+    }
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "line {}, column {}: {}",
+            self.line, self.column, self.kind
+        )
+    }
+}
+
+impl std::error::Error for ParseError {}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(super) enum Token {
+    Open,          // [
+    Close,         // ]
+    LParen,        // (
+    RParen,        // )
+    Comma,         // ,
+    Ident(String), // matrix, ZZ, ..
+    Entry(String), // anything number-like, validated when converted
+    End,
+}
+
+impl fmt::Display for Token {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Token::Open => write!(f, "`[`"),
+            Token::Close => write!(f, "`]`"),
+            Token::LParen => write!(f, "`(`"),
+            Token::RParen => write!(f, "`)`"),
+            Token::Comma => write!(f, "`,`"),
+            Token::Ident(s) | Token::Entry(s) => write!(f, "`{}`", s),
+            Token::End => write!(f, "end of input"),
+        }
+    }
+}
+
+/// A stream of tokens with their line and column
+pub(super) struct Cursor {
+    tokens: Vec<(Token, usize, usize)>,
+    pos: usize,
+}
+
+impl Cursor {
+    /// Split the input into tokens, skipping whitespace
+    pub fn new(s: &str) -> Result<Self, ParseError> {
+        let mut tokens = vec![];
+        let (mut line, mut column) = (1, 1);
+        let mut chars = s.chars().peekable();
+        while let Some(c) = chars.next() {
+            let (l, col) = (line, column);
+            column += 1;
+            let token = match c {
+                '\n' => {
+                    line += 1;
+                    column = 1;
+                    continue;
+                }
+                c if c.is_whitespace() => continue,
+                '[' => Token::Open,
+                ']' => Token::Close,
+                '(' => Token::LParen,
+                ')' => Token::RParen,
+                ',' => Token::Comma,
+                c if c.is_alphabetic() || c == '_' => {
+                    let mut s = c.to_string();
+                    while let Some(c) = chars.next_if(|c| c.is_alphanumeric() || *c == '_') {
+                        s.push(c);
+                        column += 1;
+                    }
+                    Token::Ident(s)
+                }
+                c if c.is_ascii_digit() || c == '-' || c == '+' || c == '.' => {
+                    let mut s = c.to_string();
+                    while let Some(c) =
+                        chars.next_if(|c| c.is_alphanumeric() || ".+-/".contains(*c))
+                    {
+                        s.push(c);
+                        column += 1;
+                    }
+                    Token::Entry(s)
+                }
+                c => {
+                    return Err(ParseError {
+                        line: l,
+                        column: col,
+                        kind: ParseErrorKind::Unexpected {
+                            expected: "a matrix",
+                            found: format!("`{}`", c),
+                        },
+                    })
+                }
+            };
+            tokens.push((token, l, col));
+        }
+        tokens.push((Token::End, line, column));
+        Ok(Self { tokens, pos: 0 })
+    }
+
+    pub fn peek(&self) -> &Token {
+        &self.tokens[self.pos].0
+    }
+
+    pub fn peek_second(&self) -> &Token {
+        &self.tokens[(self.pos + 1).min(self.tokens.len() - 1)].0
+    }
+
+    pub fn next(&mut self) -> Token {
+        let t = self.tokens[self.pos].0.clone();
+        self.pos = (self.pos + 1).min(self.tokens.len() - 1);
+        t
+    }
+
+    /// An error at the current token
+    pub fn error(&self, kind: ParseErrorKind) -> ParseError {
+        let (_, line, column) = self.tokens[self.pos];
+        ParseError { line, column, kind }
+    }
+
+    /// An error for an unexpected current token
+    pub fn unexpected(&self, expected: &'static str) -> ParseError {
+        self.error(ParseErrorKind::Unexpected {
+            expected,
+            found: self.peek().to_string(),
+        })
+    }
+
+    pub fn expect(&mut self, t: Token, expected: &'static str) -> Result<(), ParseError> {
+        match *self.peek() == t {
+            true => {
+                self.next();
+                Ok(())
+            }
+            false => Err(self.unexpected(expected)),
+        }
+    }
+
+    /// Parse an integer entry of type T
+    pub fn entry<T: FromStr>(&mut self) -> Result<T, ParseError> {
+        let s = match self.peek() {
+            Token::Entry(s) => s.clone(),
+            _ => return Err(self.unexpected("an integer")),
+        };
+        let digits = s.strip_prefix(['-', '+']).unwrap_or(&s);
+        if digits.is_empty() || !digits.chars().all(|c| c.is_ascii_digit()) {
+            return Err(self.error(ParseErrorKind::InvalidEntry(s)));
+        }
+        let x = T::from_str(&s).map_err(|_| self.error(ParseErrorKind::OutOfRange(s)))?;
+        self.next();
+        Ok(x)
+    }
+
+    /// Parse a row `[a b c]`, or `[a, b, c]` with optional trailing comma
+    pub fn row<T: FromStr>(&mut self, commas: bool) -> Result<(Vec<T>, usize, usize), ParseError> {
+        let (_, line, column) = self.tokens[self.pos];
+        self.expect(Token::Open, "`[`")?;
+        let mut row = vec![];
+        while *self.peek() != Token::Close {
+            row.push(self.entry()?);
+            if commas {
+                match self.peek() {
+                    Token::Comma => {
+                        self.next();
+                    }
+                    Token::Close => {}
+                    _ => return Err(self.unexpected("`,` or `]`")),
+                }
+            }
+        }
+        self.next();
+        Ok((row, line, column))
+    }
+}
+
+/// Collect rows into a lattice, checking that the matrix is rectangular
+pub(super) fn build<T: Ring>(
+    rows: Vec<(Vec<T>, usize, usize)>,
+    end: &Cursor,
+) -> Result<Lattice<T>, ParseError> {
+    let cols = match rows.first() {
+        Some((r, _, _)) if !r.is_empty() => r.len(),
+        _ => return Err(end.error(ParseErrorKind::Empty)),
+    };
+    let mut basis = vec![];
+    for (i, (row, line, column)) in rows.into_iter().enumerate() {
+        if row.len() != cols {
+            return Err(ParseError {
+                line,
+                column,
+                kind: ParseErrorKind::RowLength {
+                    row: i,
+                    expected: cols,
+                    found: row.len(),
+                },
+            });
+        }
+        let norm = T::checked_dot(&row, &row).map_err(|_| ParseError {
+            line,
+            column,
+            kind: ParseErrorKind::OutOfRange(format!("‖b_{}‖²", i)),
+        })?;
+        basis.push(Vector {
+            vec: row,
+            norm: Some(norm),
+        });
+    }
+    Ok(Lattice { basis })
+}
@@ -0,0 +1,234 @@
+use super::parser::{build, Cursor, ParseError, ParseErrorKind, Token};
+use crate::{Lattice, Ring};
+use std::fmt::Display;
+use std::str::FromStr;
+
+/**
+
+Read and write bases as Sage matrices
+
+Accepted are the literals `matrix(ZZ, [[1, 2], [3, 4]])`, `matrix([[1, 2],
+[3, 4]])`, `matrix(ZZ, 2, 2, [1, 2, 3, 4])` and `[[1, 2], [3, 4]]`, as well
+as Sage's printed form with one bracketed row per line. Bases are written
+as `matrix(ZZ, [[..], ..])` literals that Sage evaluates back to the same
+matrix.
+
+# Examples
+
+```rust
+use svp::{Lattice, Sage};
+
+let l = Lattice::<i64>::from_sage("matrix(ZZ, 2, 3, [1, 2, 3, 4, 5, 6])").unwrap();
+assert_eq!(l.to_sage(), "matrix(ZZ, [[1, 2, 3], [4, 5, 6]])");
+
+// The output of print(M)
+let m = Lattice::<i64>::from_sage("[1 2 3]\n[4 5 6]").unwrap();
+assert_eq!(m.basis[1].vec, l.basis[1].vec);
+
+let err = Lattice::<i64>::from_sage("matrix(QQ, [[1/2]])").unwrap_err();
+assert_eq!(err.to_string(), "line 1, column 8: unsupported base ring `QQ`, expected ZZ");
+```
+**/
+pub trait Sage: Sized {
+    /// Parse a basis from a Sage matrix literal or printed matrix
+    fn from_sage(s: &str) -> Result<Self, ParseError>;
+
+    /// Write the basis as a Sage matrix literal
+    fn to_sage(&self) -> String;
+}
+
+type Rows<T> = Vec<(Vec<T>, usize, usize)>;
+
+/// Parse `[[a, b], [c, d]]`
+fn nested<T: FromStr>(c: &mut Cursor) -> Result<Rows<T>, ParseError> {
+    c.expect(Token::Open, "`[`")?;
+    let mut rows = vec![];
+    while *c.peek() != Token::Close {
+        if *c.peek() != Token::Open {
+            return Err(c.unexpected("`[` or `]`"));
+        }
+        rows.push(c.row(true)?);
+        match c.peek() {
+            Token::Comma => {
+                c.next();
+            }
+            Token::Close => {}
+            _ => return Err(c.unexpected("`,` or `]`")),
+        }
+    }
+    c.next();
+    Ok(rows)
+}
+
+/// Parse the arguments of `matrix(..)` up to the closing parenthesis
+fn arguments<T: FromStr>(c: &mut Cursor) -> Result<Rows<T>, ParseError> {
+    if let Token::Ident(ring) = c.peek().clone() {
+        if ring != "ZZ" {
+            return Err(c.error(ParseErrorKind::UnsupportedRing(ring)));
+        }
+        c.next();
+        c.expect(Token::Comma, "`,`")?;
+    }
+    if !matches!(c.peek(), Token::Entry(_)) {
+        return nested(c);
+    }
+
+    // n, m, [entries]
+    let rows: usize = c.entry()?;
+    c.expect(Token::Comma, "`,`")?;
+    let cols: usize = c.entry()?;
+    c.expect(Token::Comma, "`,`")?;
+    let (flat, line, column) = c.row(true)?;
+    if rows.checked_mul(cols) != Some(flat.len()) || cols == 0 {
+        return Err(ParseError {
+            line,
+            column,
+            kind: ParseErrorKind::Dimensions {
+                rows,
+                cols,
+                entries: flat.len(),
+            },
+        });
+    }
+    let mut flat = flat.into_iter();
+    Ok((0..rows)
+        .map(|_| ((&mut flat).take(cols).collect(), line, column))
+        .collect())
+}
+
+impl<T> Sage for Lattice<T>
+where
+    T: Ring + FromStr + Display,
+{
+    fn from_sage(s: &str) -> Result<Self, ParseError> {
+        let mut c = Cursor::new(s)?;
+        let rows = match (c.peek().clone(), c.peek_second()) {
+            (Token::Ident(name), _) => {
+                if name != "matrix" && name != "Matrix" {
+                    return Err(c.unexpected("`matrix`"));
+                }
+                c.next();
+                c.expect(Token::LParen, "`(`")?;
+                let rows = arguments(&mut c)?;
+                c.expect(Token::RParen, "`)`")?;
+                rows
+            }
+            (Token::Open, Token::Open) => nested(&mut c)?,
+            (Token::Open, _) => {
+                let mut rows = vec![];
+                while *c.peek() != Token::End {
+                    rows.push(c.row(false)?);
+                }
+                rows
+            }
+            _ => return Err(c.unexpected("a matrix")),
+        };
+        c.expect(Token::End, "end of input")?;
+        build(rows, &c)
+    }
+
+    fn to_sage(&self) -> String {
+        let rows: Vec<String> = self
+            .basis
+            .iter()
+            .map(|v| {
+                let row: Vec<String> = v.vec.iter().map(|x| x.to_string()).collect();
+                format!("[{}]", row.join(", "))
+            })
+            .collect();
+        format!("matrix(ZZ, [{}])", rows.join(", "))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::*;
+    use rug::Integer;
+
+    #[test]
+    fn test_prim() {
+        let expected = vec![vec![1, 0, 1345], vec![0, 1, 35], vec![0, 0, 2048]];
+        for s in [
+            "matrix(ZZ, [[1, 0, 1345], [0, 1, 35], [0, 0, 2048]])",
+            "Matrix([[1,0,1345],\n [0,1,35],\n [0,0,2048],])",
+            "matrix(ZZ, 3, 3, [1, 0, 1345, 0, 1, 35, 0, 0, 2048])",
+            "[[1, 0, 1345], [0, 1, 35], [0, 0, 2048]]",
+            "[   1    0 1345]\n[   0    1   35]\n[   0    0 2048]\n",
+        ] {
+            let l = Lattice::<i64>::from_sage(s).unwrap();
+            let rows: Vec<Vec<i64>> = l.basis.iter().map(|v| v.vec.clone()).collect();
+            assert_eq!(rows, expected);
+            assert_eq!(
+                l.to_sage(),
+                "matrix(ZZ, [[1, 0, 1345], [0, 1, 35], [0, 0, 2048]])"
+            );
+        }
+
+        // Sage and fplll agree on the same basis
+        let l = Lattice::<i64>::from_sage("[[2, -1], [-1, 2]]").unwrap();
+        assert_eq!(
+            Lattice::<i64>::from_fplll(&l.to_fplll()).unwrap().to_sage(),
+            l.to_sage()
+        );
+    }
+
+    #[test]
+    fn test_errors() {
+        let kind = |s: &str| Lattice::<i64>::from_sage(s).unwrap_err().kind;
+        assert_eq!(kind("matrix(ZZ, [])"), ParseErrorKind::Empty);
+        assert_eq!(
+            kind("[1 2]\n[3 4 5]").to_string(),
+            "row 1 has 3 entries but the first row has 2"
+        );
+        assert_eq!(
+            kind("matrix(ZZ, 2, 2, [1, 2, 3])"),
+            ParseErrorKind::Dimensions {
+                rows: 2,
+                cols: 2,
+                entries: 3
+            }
+        );
+        assert_eq!(
+            kind("matrix(ZZ, 99999999999, 99999999999, [1])"),
+            ParseErrorKind::Dimensions {
+                rows: 99999999999,
+                cols: 99999999999,
+                entries: 1
+            }
+        );
+        assert_eq!(
+            kind("matrix(RR, [[1]])"),
+            ParseErrorKind::UnsupportedRing("RR".into())
+        );
+        assert_eq!(
+            kind("[[1, 2/3]]"),
+            ParseErrorKind::InvalidEntry("2/3".into())
+        );
+        assert!(matches!(
+            kind("vector([1, 2])"),
+            ParseErrorKind::Unexpected { .. }
+        ));
+        assert!(matches!(
+            kind("matrix(ZZ, [[1, 2]]"),
+            ParseErrorKind::Unexpected { .. }
+        ));
+        assert!(matches!(kind("[[1 2]]"), ParseErrorKind::Unexpected { .. }));
+        assert!(matches!(kind("{1, 2}"), ParseErrorKind::Unexpected { .. }));
+    }
+
+    #[test]
+    fn test_mp() {
+        let big: Integer = Integer::from(1) << 200;
+        let l = Lattice {
+            basis: vec![
+                nvec![big.clone(), Integer::from(1)],
+                nvec![Integer::from(7), -big.clone()],
+            ],
+        };
+        let r = Lattice::<Integer>::from_sage(&l.to_sage()).unwrap();
+        assert_eq!(r.basis[0].vec, l.basis[0].vec);
+        assert_eq!(r.basis[1].vec, l.basis[1].vec);
+        assert_eq!(r.basis[1].norm, Some(Integer::from(big.square_ref()) + 49));
+        assert!(Lattice::<i64>::from_sage(&l.to_sage()).is_err());
+    }
+}
@@ -0,0 +1,172 @@
+use crate::{Coordinates, Fplll, Invariants, Lattice, ParseError, Vector};
+use rand::SeedableRng;
+use rand_chacha::ChaCha20Rng;
+use rug::{Float, Integer};
+
+/**
+
+Instances of the Darmstadt SVP challenge
+
+Challenge lattices are Goldstein-Mayer lattices of dimension n with a
+prime volume of 10n bits, and a lattice vector v solves an instance if
+‖v‖ ≤ 1.05·GH(L). Published challenge files are in the fplll/NTL format
+and can be read with `Challenge::read`.
+
+`Challenge::random_like` is not the challenge generator. It draws instances
+of the same shape with ChaCha20 instead of NTL's random number generator,
+so its instances differ from the published ones for the same seed. Unlike
+`StdRng`, whose algorithm may change between `rand` releases, the ChaCha20
+stream is fixed, so an instance stays reproducible from its dimension and
+seed.
+
+# Examples
+
+```rust
+use rug::Integer;
+use svp::{Challenge, Enumerate, Fplll, Invariants, Verdict, BKZ};
+
+let c = Challenge::random_like(20, 1);
+assert_eq!(c.basis.basis.len(), 20);
+
+// A shortest vector of this instance is within 1.05·GH(L)
+let mut l = c.basis.clone();
+l.bkz(10, 0.99);
+let v = l.shortest();
+assert_eq!(v.norm, Some(Integer::from(1398572)));
+assert_eq!(c.verify(&v), Verdict::Solved(c.basis.gh_ratio(&v)));
+
+// The basis vector q·e_0 is far too long
+let q = &c.basis.basis[0];
+assert_eq!(c.verify(q), Verdict::TooLong(c.basis.gh_ratio(q)));
+
+// Round trip through the challenge file format
+let d = Challenge::read(&c.basis.to_fplll()).unwrap();
+assert_eq!(d.basis.basis[3].vec, c.basis.basis[3].vec);
+```
+**/
+#[derive(Debug, Clone)]
+pub struct Challenge {
+    pub basis: Lattice<Integer>,
+    pub seed: Option<u64>, // None if read from a file
+}
+
+/// The outcome of `Challenge::verify`, along with ‖v‖ / GH(L)
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Verdict {
+    /// A nonzero lattice vector with ‖v‖ ≤ 1.05·GH(L)
+    Solved(f64),
+    /// A nonzero lattice vector longer than 1.05·GH(L)
+    TooLong(f64),
+    /// The zero vector, or a vector outside of the lattice
+    Invalid,
+}
+
+impl Challenge {
+    /// The approximation factor of the success criterion
+    pub const FACTOR: f64 = 1.05;
+
+    /// A random instance of dimension n ≥ 2 shaped like the challenge, which
+    /// is not the published instance for that seed
+    pub fn random_like(n: usize, seed: u64) -> Self {
+        assert!(n >= 2, "challenge dimensions start at 2");
+        let mut rng = ChaCha20Rng::seed_from_u64(seed);
+        Self {
+            basis: Lattice::goldstein_mayer(n, 10 * n as u32, &mut rng),
+            seed: Some(seed),
+        }
+    }
+
+    /// Read a challenge file in the fplll/NTL format
+    pub fn read(s: &str) -> Result<Self, ParseError> {
+        Ok(Self {
+            basis: Lattice::from_fplll(s)?,
+            seed: None,
+        })
+    }
+
+    /// The dimension of the instance
+    pub fn dim(&self) -> usize {
+        self.basis.basis.len()
+    }
+
+    /// The squared norm bound (1.05·GH(L))² of a solution
+    pub fn bound(&self, prec: u32) -> Float {
+        let gh = self.basis.gaussian_heuristic(prec) * Self::FACTOR;
+        gh.square()
+    }
+
+    /// Check whether v solves the instance
+    pub fn verify(&self, v: &Vector<Integer>) -> Verdict {
+        let norm = v * v;
+        if norm == 0 || self.basis.coordinates(v).is_none() {
+            return Verdict::Invalid;
+        }
+        let ratio = self.basis.gh_ratio(v);
+        // Compare squared norms exactly up to the precision of the bound
+        match Float::with_val(128, &norm) <= self.bound(128) {
+            true => Verdict::Solved(ratio),
+            false => Verdict::TooLong(ratio),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::*;
+    use rug::Integer;
+
+    #[test]
+    fn test_mp() {
+        let c = Challenge::random_like(24, 42);
+        assert_eq!((c.dim(), c.seed), (24, Some(42)));
+        assert_eq!(c.basis.basis[0].vec[0].significant_bits(), 240);
+        assert!(c
+            .basis
+            .basis
+            .iter()
+            .zip(&Challenge::random_like(24, 42).basis.basis)
+            .all(|(u, v)| u.vec == v.vec));
+
+        // A shortest vector of this instance, at about 1.045·GH(L)
+        let mut l = c.basis.clone();
+        l.bkz(10, 0.99);
+        let v = l.shortest();
+        assert_eq!(v.norm, Some(Integer::from(1894191)));
+        match c.verify(&v) {
+            Verdict::Solved(r) => assert!(r > 1.04 && r < 1.05),
+            verdict => panic!("{:?} for a vector of norm √1894191", verdict),
+        }
+
+        // q·e_5 lies in the lattice, with norm q
+        let q = c.basis.basis[0].vec[0].clone();
+        let mut w = nvec![Integer::new(); 24];
+        w.vec[5] = q;
+        assert!(matches!(c.verify(&w), Verdict::TooLong(r) if r > 1e6));
+
+        // The zero vector and vectors outside of the lattice are invalid
+        let mut w = v.clone();
+        w.vec[1] += 1;
+        assert_eq!(c.verify(&w), Verdict::Invalid);
+        assert_eq!(c.verify(&nvec![Integer::new(); 24]), Verdict::Invalid);
+    }
+
+    #[test]
+    fn test_read() {
+        // A 2-dimensional instance as it would appear in a challenge file
+        let c = Challenge::read("[[1000003 0]\n[333334 1]\n]\n").unwrap();
+        assert_eq!((c.dim(), c.seed), (2, None));
+
+        // 3·(333334, 1) - (1000003, 0) = (-1, 3)
+        let v = nvec![Integer::from(-1), Integer::from(3)];
+        match c.verify(&v) {
+            Verdict::Solved(r) => assert!(r < 0.1),
+            _ => panic!("(-1, 3) solves the instance"),
+        }
+        assert_eq!(
+            c.verify(&nvec![Integer::from(1), Integer::new()]),
+            Verdict::Invalid
+        );
+        assert!(matches!(c.verify(&c.basis.basis[0]), Verdict::TooLong(_)));
+        assert!(Challenge::read("[[1 2]").is_err());
+    }
+}
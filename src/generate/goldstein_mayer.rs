@@ -0,0 +1,92 @@
+use super::{random_below, random_prime};
+use crate::{nvec, Lattice, Vector};
+use rand::RngCore;
+use rug::Integer;
+
+/**
+
+Goldstein-Mayer random lattices \[GM03\]
+
+For a prime q with the given number of bits, the basis
+
+```text
+q    0 0 .. 0
+x_1  1 0 .. 0
+x_2  0 1 .. 0
+..
+x_n' 0 0 .. 1
+```
+
+with x_i uniform in [0, q) spans a lattice of volume q, which is in a
+sense a uniformly random lattice of that volume. These are the lattices of
+the Darmstadt SVP challenge, with q of 10n bits.
+
+# Examples
+
+```rust
+use rand::SeedableRng;
+use rand_chacha::ChaCha20Rng;
+use rug::Integer;
+use svp::{Invariants, Lattice};
+
+let mut rng = ChaCha20Rng::seed_from_u64(1);
+let l = Lattice::goldstein_mayer(8, 40, &mut rng);
+let q = l.basis[0].vec[0].clone();
+assert_eq!(q.significant_bits(), 40);
+assert_eq!(l.gram_det(), Integer::from(q.square_ref()));
+```
+**/
+impl Lattice<Integer> {
+    /// A random n-dimensional Goldstein-Mayer basis of volume q, a random
+    /// prime with `bits` bits
+    pub fn goldstein_mayer(n: usize, bits: u32, rng: &mut dyn RngCore) -> Lattice<Integer> {
+        assert!(n > 0, "the dimension must be positive");
+        let q = random_prime(bits, rng);
+        let mut basis = vec![];
+        for i in 0..n {
+            let mut v = nvec![Integer::new(); n];
+            match i {
+                0 => v.vec[0] = q.clone(),
+                _ => {
+                    v.vec[0] = random_below(&q, rng);
+                    v.vec[i] = Integer::from(1);
+                }
+            }
+            v.norm = Some(&v * &v);
+            basis.push(v);
+        }
+        Lattice { basis }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::*;
+    use rand::SeedableRng;
+    use rand_chacha::ChaCha20Rng;
+    use rug::Integer;
+
+    #[test]
+    fn test_mp() {
+        // Seeded generation is reproducible
+        let gen = |seed| Lattice::goldstein_mayer(12, 120, &mut ChaCha20Rng::seed_from_u64(seed));
+        let (a, b, c) = (gen(5), gen(5), gen(6));
+        assert!(a.basis.iter().zip(&b.basis).all(|(u, v)| u.vec == v.vec));
+        assert!(a.basis.iter().zip(&c.basis).any(|(u, v)| u.vec != v.vec));
+
+        let q = a.basis[0].vec[0].clone();
+        assert!(q.is_probably_prime(30) != rug::integer::IsPrime::No);
+        for (i, v) in a.basis.iter().enumerate().skip(1) {
+            assert!(v.vec[0] < q && v.vec[0] >= 0);
+            assert_eq!(v.vec[i], 1);
+            assert_eq!(v.norm, Some(v * v));
+        }
+        assert_eq!(a.gram_det(), Integer::from(q.square_ref()));
+
+        // The reduced basis is close to the gaussian heuristic
+        let mut r = a.clone();
+        r.bkz(12, 0.99);
+        let ratio = r.gh_ratio(&r.basis[0]);
+        assert!(ratio > 0.5 && ratio < 1.5);
+    }
+}
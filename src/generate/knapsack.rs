@@ -0,0 +1,97 @@
+use super::random_bits;
+use crate::{nvec, Lattice, Vector};
+use rand::RngCore;
+use rug::Integer;
+
+/**
+
+Knapsack-type lattices
+
+For n random weights a_i with `bits` bits the basis
+
+```text
+a_1 1 0 .. 0
+a_2 0 1 .. 0
+..
+a_n 0 0 .. 1
+```
+
+spans the lattice of the vectors (Σ x_i a_i, x_1, .., x_n), whose short
+vectors are the small integer relations among the weights. These are the
+knapsack-like bases of fplll's `latticegen r`, and the same construction
+with an extra row (s, 0, .., 0) underlies the Lagarias-Odlyzko attack on
+subset sum. The rank is n in Z^{n+1}.
+
+# Examples
+
+```rust
+use rand::SeedableRng;
+use rand_chacha::ChaCha20Rng;
+use rug::Integer;
+use svp::{Enumerate, Lattice, LLL};
+
+let mut rng = ChaCha20Rng::seed_from_u64(1);
+let l = Lattice::knapsack(10, 20, &mut rng);
+assert_eq!(l.basis.len(), 10);
+assert_eq!(l.basis[0].vec.len(), 11);
+
+// A shortest vector (Σ x_i a_i, x_1, .., x_n) of the reduced basis
+let mut r = l.clone();
+r.lll(0.99);
+let v = r.shortest();
+let mut sum = Integer::new();
+for (x, b) in v.vec[1..].iter().zip(&l.basis) {
+    sum += Integer::from(x * &b.vec[0]);
+}
+assert_eq!(v.vec[0], sum);
+```
+**/
+impl Lattice<Integer> {
+    /// A random n-dimensional knapsack basis with weights of `bits` bits
+    pub fn knapsack(n: usize, bits: u32, rng: &mut dyn RngCore) -> Lattice<Integer> {
+        assert!(n > 0, "the dimension must be positive");
+        let mut basis = vec![];
+        for i in 0..n {
+            let mut v = nvec![Integer::new(); n + 1];
+            v.vec[0] = random_bits(bits, rng);
+            v.vec[i + 1] = Integer::from(1);
+            v.norm = Some(&v * &v);
+            basis.push(v);
+        }
+        Lattice { basis }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::*;
+    use rand::SeedableRng;
+    use rand_chacha::ChaCha20Rng;
+    use rug::Integer;
+
+    #[test]
+    fn test_mp() {
+        let gen = |seed| Lattice::knapsack(16, 64, &mut ChaCha20Rng::seed_from_u64(seed));
+        let (a, b, c) = (gen(2), gen(2), gen(4));
+        assert!(a.basis.iter().zip(&b.basis).all(|(u, v)| u.vec == v.vec));
+        assert!(a.basis.iter().zip(&c.basis).any(|(u, v)| u.vec != v.vec));
+
+        for (i, v) in a.basis.iter().enumerate() {
+            assert!(v.vec[0] >= 0 && v.vec[0].significant_bits() <= 64);
+            assert_eq!(v.vec[i + 1], 1);
+            assert_eq!(v.norm, Some(v * v));
+        }
+
+        // Reduced vectors keep the form (Σ x_i a_i, x_1, .., x_n)
+        let mut r = a.clone();
+        r.lll(0.99);
+        assert!(r.same_lattice(&a));
+        for v in &r.basis {
+            let mut sum = Integer::new();
+            for (i, x) in v.vec[1..].iter().enumerate() {
+                sum += Integer::from(x * &a.basis[i].vec[0]);
+            }
+            assert_eq!(sum, v.vec[0]);
+        }
+    }
+}
@@ -0,0 +1,47 @@
+mod challenge;
+mod goldstein_mayer;
+mod knapsack;
+mod q_ary;
+mod unimodular;
+
+pub use challenge::{Challenge, Verdict};
+
+use rand::RngCore;
+use rug::integer::Order;
+use rug::Integer;
+
+/// A uniformly random integer in [0, 2^bits)
+pub(crate) fn random_bits(bits: u32, rng: &mut dyn RngCore) -> Integer {
+    let mut words = vec![0u64; bits.div_ceil(64) as usize];
+    for w in words.iter_mut() {
+        *w = rng.next_u64();
+    }
+    let mut x = Integer::from_digits(&words, Order::Lsf);
+    x.keep_bits_mut(bits);
+    x
+}
+
+/// A uniformly random integer in [0, bound), by rejection sampling
+pub(crate) fn random_below(bound: &Integer, rng: &mut dyn RngCore) -> Integer {
+    assert!(*bound > 0, "the bound must be positive");
+    let bits = bound.significant_bits();
+    loop {
+        let x = random_bits(bits, rng);
+        if x < *bound {
+            return x;
+        }
+    }
+}
+
+/// A uniformly random prime with exactly `bits` bits
+pub(crate) fn random_prime(bits: u32, rng: &mut dyn RngCore) -> Integer {
+    assert!(bits >= 2, "there are no primes with fewer than 2 bits");
+    loop {
+        let mut x = random_bits(bits - 1, rng);
+        x.set_bit(bits - 1, true);
+        let p = x.next_prime();
+        if p.significant_bits() == bits {
+            return p;
+        }
+    }
+}
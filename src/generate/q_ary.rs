@@ -0,0 +1,195 @@
+use super::random_below;
+use crate::{nvec, Dual, Lattice, Vector};
+use rand::RngCore;
+use rug::Integer;
+
+/**
+
+q-ary lattices of a matrix A ∈ Z_q^{k×m}
+
+The lattices
+
+```text
+Λ_q(A) = { y ∈ Z^m : y ≡ Aᵀ·s mod q for some s ∈ Z^k }
+Λ_q^⊥(A) = { y ∈ Z^m : A·y ≡ 0 mod q }
+```
+
+both contain qZ^m and are dual to each other up to scaling,
+Λ_q^⊥(A) = q·Λ_q(A)*. They underlie the SIS and LWE problems. The matrix
+A is given by its k rows of length m, and the bases are returned in
+Hermite normal form. For a uniformly random A with k ≤ m the random
+variants build the bases directly in the systematic form of A = [I | A']
+instead, which is what A is up to a change of basis of Z_q^k with
+overwhelming probability for prime q:
+
+```text
+Λ_q(A):  I_k  A'           Λ_q^⊥(A):  q·I_k     0
+         0    q·I_{m-k}               -A'ᵀ mod q  I_{m-k}
+```
+
+# Examples
+
+```rust
+use rand::SeedableRng;
+use rand_chacha::ChaCha20Rng;
+use rug::Integer;
+use svp::{nvec, Invariants, Lattice, Vector};
+
+let q = Integer::from(7);
+let a = vec![nvec![Integer::from(1), Integer::from(3)]];
+
+// Λ_q(A) is spanned by (1, 3) and 7·Z^2, Λ_q^⊥(A) by (-3, 1) and 7·Z^2
+let l = Lattice::q_ary(&a, &q);
+assert_eq!(l.basis[0].vec, vec![1, 3]);
+assert_eq!(l.basis[1].vec, vec![0, 7]);
+let p = Lattice::q_ary_perp(&a, &q);
+assert_eq!(p.basis[0].vec, vec![1, 2]);
+assert_eq!(p.basis[1].vec, vec![0, 7]);
+
+// Random lattices of dimension m = 8 and volume q^{m-k} resp. q^k
+let mut rng = ChaCha20Rng::seed_from_u64(1);
+let l = Lattice::random_q_ary(3, 8, &q, &mut rng);
+assert_eq!(l.gram_det(), 7i64.pow(2 * 5));
+let p = Lattice::random_q_ary_perp(3, 8, &q, &mut rng);
+assert_eq!(p.gram_det(), 7i64.pow(2 * 3));
+```
+**/
+impl Lattice<Integer> {
+    /// The q-ary lattice Λ_q(A) spanned by the rows of A and qZ^m
+    pub fn q_ary(a: &[Vector<Integer>], q: &Integer) -> Lattice<Integer> {
+        assert!(*q > 0, "the modulus must be positive");
+        let m = a.first().expect("the matrix must have a row").vec.len();
+        let mut basis = a.to_vec();
+        for i in 0..m {
+            let mut v = nvec![Integer::new(); m];
+            v.vec[i] = q.clone();
+            basis.push(v);
+        }
+        with_norms(Lattice { basis }.canonical())
+    }
+
+    /// The q-ary lattice Λ_q^⊥(A) of the y with A·y ≡ 0 mod q
+    pub fn q_ary_perp(a: &[Vector<Integer>], q: &Integer) -> Lattice<Integer> {
+        // q·Λ_q(A)* is integral, so the scale of the dual basis divides q
+        let dual = Lattice::q_ary(a, q).dual();
+        let f = Integer::from(q / &dual.scale);
+        let mut l = dual.basis;
+        for v in l.basis.iter_mut() {
+            for x in v.vec.iter_mut() {
+                *x *= &f;
+            }
+        }
+        with_norms(l.canonical())
+    }
+
+    /// The basis of Λ_q(A) for a uniform A = [I | A'] ∈ Z_q^{k×m}
+    pub fn random_q_ary(
+        k: usize,
+        m: usize,
+        q: &Integer,
+        rng: &mut dyn RngCore,
+    ) -> Lattice<Integer> {
+        let a = systematic(k, m, q, rng);
+        let mut basis = vec![];
+        for i in 0..m {
+            let mut v = nvec![Integer::new(); m];
+            match i < k {
+                true => {
+                    v.vec[i] = Integer::from(1);
+                    v.vec[k..].clone_from_slice(&a[i]);
+                }
+                false => v.vec[i] = q.clone(),
+            }
+            basis.push(v);
+        }
+        with_norms(Lattice { basis })
+    }
+
+    /// The basis of Λ_q^⊥(A) for a uniform A = [I | A'] ∈ Z_q^{k×m}
+    pub fn random_q_ary_perp(
+        k: usize,
+        m: usize,
+        q: &Integer,
+        rng: &mut dyn RngCore,
+    ) -> Lattice<Integer> {
+        let a = systematic(k, m, q, rng);
+        let mut basis = vec![];
+        for i in 0..m {
+            let mut v = nvec![Integer::new(); m];
+            match i < k {
+                true => v.vec[i] = q.clone(),
+                false => {
+                    for j in 0..k {
+                        v.vec[j] = Integer::from(q - &a[j][i - k]) % q;
+                    }
+                    v.vec[i] = Integer::from(1);
+                }
+            }
+            basis.push(v);
+        }
+        with_norms(Lattice { basis })
+    }
+}
+
+/// The uniformly random k×(m-k) block A' of A = [I | A']
+fn systematic(k: usize, m: usize, q: &Integer, rng: &mut dyn RngCore) -> Vec<Vec<Integer>> {
+    assert!(*q > 1, "the modulus must be at least 2");
+    assert!(0 < k && k <= m, "A must have between 1 and m rows");
+    (0..k)
+        .map(|_| (k..m).map(|_| random_below(q, rng)).collect())
+        .collect()
+}
+
+fn with_norms(mut l: Lattice<Integer>) -> Lattice<Integer> {
+    for v in l.basis.iter_mut() {
+        v.norm = Some(&*v * &*v);
+    }
+    l
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::*;
+    use rand::SeedableRng;
+    use rand_chacha::ChaCha20Rng;
+    use rug::Integer;
+
+    #[test]
+    fn test_mp() {
+        let q = Integer::from(13);
+        let gen = |seed| Lattice::random_q_ary(4, 10, &q, &mut ChaCha20Rng::seed_from_u64(seed));
+        let (a, b) = (gen(3), gen(3));
+        assert!(a.basis.iter().zip(&b.basis).all(|(u, v)| u.vec == v.vec));
+
+        // The random bases agree with the constructions from A = [I | A']
+        let rows: Vec<Vector<Integer>> = a.basis[..4].to_vec();
+        assert!(a.same_lattice(&Lattice::q_ary(&rows, &q)));
+        let p = Lattice::random_q_ary_perp(4, 10, &q, &mut ChaCha20Rng::seed_from_u64(3));
+        assert!(p.same_lattice(&Lattice::q_ary_perp(&rows, &q)));
+
+        // A·y ≡ 0 mod q on Λ_q^⊥(A), and ⟨x, y⟩ ≡ 0 mod q across both
+        for y in &p.basis {
+            assert_eq!(y.norm, Some(y * y));
+            for x in &a.basis {
+                assert!((x * y).is_divisible(&q));
+            }
+        }
+
+        // A rank deficient A over a composite modulus
+        let q = Integer::from(12);
+        let a = vec![
+            nvec![Integer::from(2), Integer::from(4), Integer::from(6)],
+            nvec![Integer::from(4), Integer::from(8), Integer::from(12)],
+        ];
+        let l = Lattice::q_ary(&a, &q);
+        let p = Lattice::q_ary_perp(&a, &q);
+        // A reaches 6 residues mod 12, so vol Λ_q(A) = 12^3 / 6 = 288
+        assert_eq!(l.gram_det(), 288 * 288);
+        assert_eq!(p.gram_det(), 6 * 6);
+        for y in &p.basis {
+            for x in &a {
+                assert!((x * y).is_divisible(&q));
+            }
+        }
+    }
+}
@@ -0,0 +1,104 @@
+use crate::{Lattice, Vector};
+use rand::{Rng, RngCore};
+use rug::Integer;
+
+/**
+
+Random bases of a lattice
+
+The new basis is U·B for a random unimodular U = P·L·R, where R and L are
+upper and lower unitriangular with entries in {-1, 0, 1} and P is a random
+permutation. The entries of U stay polynomial in the dimension, so the
+result hides the structure of the input basis, e.g. a reduced one, without
+growing its bit size much. This is the usual way to obtain hard-looking
+bases of lattices with a known short basis.
+
+# Examples
+
+```rust
+use rand::SeedableRng;
+use rand_chacha::ChaCha20Rng;
+use rug::Integer;
+use svp::{nvec, Lattice, Vector};
+
+let l = Lattice {
+    basis: vec![
+        nvec![Integer::from(2), Integer::new(), Integer::new()],
+        nvec![Integer::new(), Integer::from(3), Integer::new()],
+        nvec![Integer::new(), Integer::new(), Integer::from(5)],
+    ],
+};
+
+let mut rng = ChaCha20Rng::seed_from_u64(1);
+let r = l.random_unimodular(&mut rng);
+assert!(r.same_lattice(&l));
+```
+**/
+impl Lattice<Integer> {
+    /// A random basis of the same lattice
+    pub fn random_unimodular(&self, rng: &mut dyn RngCore) -> Lattice<Integer> {
+        let n = self.basis.len();
+        let mut basis = self.basis.clone();
+
+        // b_i += Σ_{j>i} r_ij b_j, rows below i are not changed yet
+        for i in 0..n {
+            for j in i + 1..n {
+                add_row(&mut basis, i, j, rng.gen_range(-1..=1));
+            }
+        }
+        // b_i += Σ_{j<i} l_ij b_j, rows above i are not changed yet
+        for i in (0..n).rev() {
+            for j in 0..i {
+                add_row(&mut basis, i, j, rng.gen_range(-1..=1));
+            }
+        }
+        for i in (1..n).rev() {
+            basis.swap(i, rng.gen_range(0..=i));
+        }
+
+        for v in basis.iter_mut() {
+            v.norm = Some(&*v * &*v);
+        }
+        Lattice { basis }
+    }
+}
+
+/// Add c times row b to row a
+fn add_row(m: &mut [Vector<Integer>], a: usize, b: usize, c: i32) {
+    if c == 0 {
+        return;
+    }
+    for k in 0..m[a].vec.len() {
+        let x = Integer::from(&m[b].vec[k] * c);
+        m[a].vec[k] += x;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::*;
+    use rand::SeedableRng;
+    use rand_chacha::ChaCha20Rng;
+
+    #[test]
+    fn test_mp() {
+        let mut rng = ChaCha20Rng::seed_from_u64(7);
+        let l = Lattice::goldstein_mayer(12, 30, &mut rng);
+        let mut r = l.random_unimodular(&mut rng);
+        assert!(r.same_lattice(&l));
+        assert_eq!(r.gram_det(), l.gram_det());
+        assert!(r.basis.iter().all(|v| v.norm == Some(v * v)));
+
+        // Seeded randomization is reproducible
+        let a = l.random_unimodular(&mut ChaCha20Rng::seed_from_u64(1));
+        let b = l.random_unimodular(&mut ChaCha20Rng::seed_from_u64(1));
+        assert!(a.basis.iter().zip(&b.basis).all(|(u, v)| u.vec == v.vec));
+
+        // Reduction recovers a basis of the same quality
+        let mut s = l.clone();
+        s.lll(0.99);
+        r.lll(0.99);
+        assert!(r.same_lattice(&s));
+        assert_eq!(r.shortest().norm, s.shortest().norm);
+    }
+}
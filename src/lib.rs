@@ -1,7 +1,23 @@
 mod algebra;
+mod enumerate;
+mod format;
+mod generate;
+mod reduce;
 mod sample;
 mod sieve;
+#[cfg(test)]
+mod testing;
 
-pub use algebra::{GaussReduce, Lattice, Vector, GSO};
-pub use sample::{KleinSampler, Sample};
-pub use sieve::{GaussSieve, Sieve};
+#[cfg(feature = "serde")]
+pub use algebra::SerdeScalar;
+pub use algebra::{
+    Coordinates, Dual, DualBasis, ExactGSO, Field, GaussReduce, GramLattice, HermiteForm,
+    IntegralGSO, Invariants, Lattice, NormalForm, Overflow, Profile, QualityProfile, Ring, Scalar,
+    SmithForm, Vector, DEFAULT_PRECISION, GSO,
+};
+pub use enumerate::{Closest, Enumerate, SuccessiveMinima};
+pub use format::{Fplll, ParseError, ParseErrorKind, Sage};
+pub use generate::{Challenge, Verdict};
+pub use reduce::{DeepLLL, PotLLL, SelfDualBKZ, SizeReduce, Slide, BKZ, HKZ, LLL};
+pub use sample::{KleinSampler, Sample, SampleGram, SampleMode};
+pub use sieve::{GaussSieve, GramSieve, Sieve, Stop};
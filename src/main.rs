@@ -0,0 +1,630 @@
+use rand::{rngs::StdRng, Rng, SeedableRng};
+use rug::{Float, Integer};
+use std::io::Read;
+use std::time::Instant;
+use svp::*;
+
+const USAGE: &str = "\
+Solve the shortest or closest vector problem for a lattice basis
+
+Usage: svp [OPTIONS] [FILE]
+
+Reads the basis from FILE, or from standard input if FILE is missing or `-`.
+The shortest vectors found are written to standard output, statistics to
+standard error.
+
+Options:
+  -f, --format <FORMAT>    Input format: fplll, sage or json
+                           [default: json for *.json files, fplll otherwise]
+  -o, --output <FORMAT>    Output format: fplll, sage or json [default: fplll]
+  -r, --reduce <METHOD>    Preprocessing: none, lll, deep-lll, bkz, slide,
+                           sd-bkz or hkz [default: lll]
+  -b, --block-size <BETA>  Block size of bkz, slide and sd-bkz [default: 10]
+  -d, --delta <DELTA>      Reduction parameter in (1/4, 1) [default: 0.99]
+  -s, --solver <SOLVER>    gauss (Gauss Sieve), gram (Gauss Sieve on the Gram
+                           matrix) or enum (enumeration) [default: gauss]
+      --seed <SEED>        Seed of the sampler [default: random]
+  -p, --precision <BITS>   Precision of the sampler, which then runs with
+                           arbitrary precision integers [default: 53]
+      --stop <STOP>        Stop criterion of the sieves: heuristic,
+                           collisions:N, norm:X (squared norm at most X) or
+                           gh:R (norm at most R times the gaussian heuristic)
+                           [default: heuristic]
+  -n, --count <N>          Number of vectors to write [default: 1]
+  -t, --target <VECTOR>    Write a lattice vector closest to VECTOR instead,
+                           given as a row such as `[1 2 3]`, found by
+                           enumeration
+  -h, --help               Print this message
+";
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Format {
+    Fplll,
+    Sage,
+    Json,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Reduce {
+    None,
+    Lll,
+    DeepLll,
+    Bkz,
+    Slide,
+    SdBkz,
+    Hkz,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Solver {
+    Gauss,
+    Gram,
+    Enum,
+}
+
+/// Stop criterion as given on the command line, before the GH is known
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum StopArg {
+    Stop(Stop),
+    Gh(f64),
+}
+
+#[derive(Debug, Clone, PartialEq)]
+struct Options {
+    input: Option<String>, // None for standard input
+    format: Option<Format>,
+    output: Format,
+    reduce: Reduce,
+    beta: usize,
+    delta: f64,
+    solver: Solver,
+    seed: Option<u64>,
+    precision: Option<u32>,
+    stop: StopArg,
+    count: usize,
+    target: Option<Vec<Integer>>, // CVP instead of SVP
+}
+
+impl Default for Options {
+    fn default() -> Self {
+        Self {
+            input: None,
+            format: None,
+            output: Format::Fplll,
+            reduce: Reduce::Lll,
+            beta: 10,
+            delta: 0.99,
+            solver: Solver::Gauss,
+            seed: None,
+            precision: None,
+            stop: StopArg::Stop(Stop::Heuristic),
+            count: 1,
+            target: None,
+        }
+    }
+}
+
+/// The outcome of a run
+#[derive(Debug)]
+struct Report {
+    vectors: Vec<Vector<Integer>>, // shortest, or closest to the target, first
+    dim: (usize, usize),
+    seed: u64,
+    reduce_secs: f64,
+    solve_secs: f64,
+    found: usize,              // size of the final list
+    root_hermite: f64,         // of the reduced basis
+    gh_ratio: f64,             // ‖v‖ / GH(L) of the shortest vector, ‖t - v‖ / GH(L) for CVP
+    distance: Option<Integer>, // ‖t - v‖² of the closest vector
+}
+
+fn parse_format(s: &str) -> Result<Format, String> {
+    match s {
+        "fplll" => Ok(Format::Fplll),
+        "sage" => Ok(Format::Sage),
+        "json" => Ok(Format::Json),
+        _ => Err(format!("unknown format `{}`", s)),
+    }
+}
+
+fn parse_number<N: std::str::FromStr>(flag: &str, s: &str) -> Result<N, String> {
+    s.parse()
+        .map_err(|_| format!("invalid value `{}` for {}", s, flag))
+}
+
+/// Parse a target vector given as a single fplll row
+fn parse_target(s: &str) -> Result<Vec<Integer>, String> {
+    let l = Lattice::<Integer>::from_fplll(&format!("[{}]", s))
+        .map_err(|e| format!("invalid target `{}`: {}", s, e.kind))?;
+    match <[Vector<Integer>; 1]>::try_from(l.basis) {
+        Ok([v]) => Ok(v.vec),
+        Err(_) => Err(format!("invalid target `{}`: expected a single row", s)),
+    }
+}
+
+fn parse_stop(s: &str) -> Result<StopArg, String> {
+    let (kind, value) = s.split_once(':').unwrap_or((s, ""));
+    match kind {
+        "heuristic" if value.is_empty() => Ok(StopArg::Stop(Stop::Heuristic)),
+        "collisions" => Ok(StopArg::Stop(Stop::Collisions(parse_number(
+            "--stop", value,
+        )?))),
+        "norm" => Ok(StopArg::Stop(Stop::Norm(parse_number("--stop", value)?))),
+        "gh" => Ok(StopArg::Gh(parse_number("--stop", value)?)),
+        _ => Err(format!("unknown stop criterion `{}`", s)),
+    }
+}
+
+/// Parse the command line, or return `Ok(None)` if help was requested
+fn parse_args(args: &[String]) -> Result<Option<Options>, String> {
+    let mut o = Options::default();
+    let mut it = args.iter();
+    while let Some(arg) = it.next() {
+        let (flag, inline) = match arg.split_once('=') {
+            Some((f, v)) if arg.starts_with("--") => (f, Some(v.to_string())),
+            _ => (arg.as_str(), None),
+        };
+        if flag == "-h" || flag == "--help" {
+            return Ok(None);
+        }
+        if !flag.starts_with('-') || flag == "-" {
+            match o.input {
+                None => o.input = Some(arg.clone()),
+                Some(_) => return Err(format!("unexpected argument `{}`", arg)),
+            }
+            continue;
+        }
+        let value = match inline {
+            Some(v) => v,
+            None => it
+                .next()
+                .cloned()
+                .ok_or_else(|| format!("missing value for {}", flag))?,
+        };
+        match flag {
+            "-f" | "--format" => o.format = Some(parse_format(&value)?),
+            "-o" | "--output" => o.output = parse_format(&value)?,
+            "-r" | "--reduce" => {
+                o.reduce = match value.as_str() {
+                    "none" => Reduce::None,
+                    "lll" => Reduce::Lll,
+                    "deep-lll" => Reduce::DeepLll,
+                    "bkz" => Reduce::Bkz,
+                    "slide" => Reduce::Slide,
+                    "sd-bkz" => Reduce::SdBkz,
+                    "hkz" => Reduce::Hkz,
+                    _ => return Err(format!("unknown reduction `{}`", value)),
+                }
+            }
+            "-b" | "--block-size" => o.beta = parse_number(flag, &value)?,
+            "-d" | "--delta" => o.delta = parse_number(flag, &value)?,
+            "-s" | "--solver" => {
+                o.solver = match value.as_str() {
+                    "gauss" => Solver::Gauss,
+                    "gram" => Solver::Gram,
+                    "enum" => Solver::Enum,
+                    _ => return Err(format!("unknown solver `{}`", value)),
+                }
+            }
+            "--seed" => o.seed = Some(parse_number(flag, &value)?),
+            "-p" | "--precision" => o.precision = Some(parse_number(flag, &value)?),
+            "--stop" => o.stop = parse_stop(&value)?,
+            "-n" | "--count" => o.count = parse_number(flag, &value)?,
+            "-t" | "--target" => o.target = Some(parse_target(&value)?),
+            _ => return Err(format!("unknown option `{}`", flag)),
+        }
+    }
+    if !(o.delta > 0.25 && o.delta < 1.0) {
+        return Err(format!("--delta must lie in (1/4, 1), got {}", o.delta));
+    }
+    if o.beta < 2 {
+        return Err(format!("--block-size must be at least 2, got {}", o.beta));
+    }
+    Ok(Some(o))
+}
+
+fn parse_json(s: &str) -> Result<Lattice<Integer>, String> {
+    use serde_json::Value;
+
+    let value: Value = serde_json::from_str(s).map_err(|e| e.to_string())?;
+    if value.is_object() {
+        return serde_json::from_value(value).map_err(|e| e.to_string());
+    }
+
+    // A plain array of rows, with entries given as numbers or strings
+    let entry = |x: &Value| match x {
+        Value::Number(n) => n.as_i64().map(Integer::from),
+        Value::String(s) => s.parse().ok(),
+        _ => None,
+    };
+    let rows = value
+        .as_array()
+        .ok_or("expected a lattice or an array of rows")?;
+    let basis = rows
+        .iter()
+        .map(|r| {
+            let vec = r
+                .as_array()
+                .ok_or(format!("expected a row but found {}", r))?
+                .iter()
+                .map(|x| entry(x).ok_or(format!("`{}` is not an integer", x)))
+                .collect::<Result<Vec<Integer>, String>>()?;
+            Ok(Vector { vec, norm: None })
+        })
+        .collect::<Result<Vec<_>, String>>()?;
+    Ok(Lattice { basis })
+}
+
+/// Parse the basis, and check that it is a nonempty rectangular matrix
+fn parse_basis(s: &str, format: Format) -> Result<Lattice<Integer>, String> {
+    let mut l = match format {
+        Format::Fplll => Lattice::from_fplll(s).map_err(|e| e.to_string())?,
+        Format::Sage => Lattice::from_sage(s).map_err(|e| e.to_string())?,
+        Format::Json => parse_json(s)?,
+    };
+    let m = l.basis.first().map(|v| v.vec.len()).unwrap_or(0);
+    if m == 0 || l.basis.iter().any(|v| v.vec.len() != m) {
+        return Err("the basis must be a nonempty rectangular matrix".into());
+    }
+    for v in l.basis.iter_mut() {
+        v.norm = Some(&*v * &*v);
+    }
+    Ok(l)
+}
+
+fn reduce(l: &mut Lattice<Integer>, o: &Options) {
+    match o.reduce {
+        Reduce::None => {}
+        Reduce::Lll => {
+            l.lll(o.delta);
+        }
+        Reduce::DeepLll => l.deep_lll(o.delta, 0),
+        Reduce::Bkz => l.bkz(o.beta, o.delta),
+        Reduce::Slide => l.slide(o.beta, o.delta),
+        Reduce::SdBkz => l.sd_bkz(o.beta, o.delta),
+        Reduce::Hkz => l.hkz(),
+    }
+}
+
+/// Run the Gauss Sieve, with machine integers unless a precision is given
+fn gauss(l: &Lattice<Integer>, o: &Options, rng: &mut StdRng, stop: Stop) -> Vec<Vector<Integer>> {
+    let t = (l.basis.len() as f64).ln();
+    if let (None, Some(b)) = (o.precision, l.narrow::<i64>()) {
+        let mut gs = gsieve![b, t];
+        return gs.sieve_or_promote_with(rng, stop);
+    }
+    let prec = o.precision.unwrap_or(53);
+    let mut gs = GaussSieve {
+        s: l.basis.clone(),
+        k: KleinSampler::init(&l.gso_prec(prec), Float::with_val(prec, t)),
+        b: l.clone(),
+        l: vec![],
+    };
+    gs.sieve_with(rng, stop)
+}
+
+/// Run the Gauss Sieve on the Gram matrix, and map the coefficient vectors
+/// back to lattice vectors
+fn gram(l: &Lattice<Integer>, o: &Options, rng: &mut StdRng, stop: Stop) -> Vec<Vector<Integer>> {
+    let t = (l.basis.len() as f64).ln();
+    let g = l.gram();
+
+    // Narrowed from the exact Gram matrix, the inner products may not fit
+    let narrow = Lattice {
+        basis: g.gram.clone(),
+    }
+    .narrow::<i64>();
+    let coefficients = match (o.precision, narrow) {
+        (None, Some(b)) => GramSieve::init(GramLattice { gram: b.basis }, t)
+            .try_sieve_with(rng, stop)
+            .map(|z| z.iter().map(Vector::from).collect()),
+        _ => Err(Overflow),
+    };
+    let coefficients = coefficients.unwrap_or_else(|_| {
+        let prec = o.precision.unwrap_or(53);
+        let mut gs = GramSieve {
+            s: (0..g.dim()).map(|i| g.unit(i)).collect(),
+            k: KleinSampler::init(&g.gso_prec(prec), Float::with_val(prec, t)),
+            g,
+            l: vec![],
+        };
+        gs.sieve_with(rng, stop)
+    });
+    coefficients
+        .iter()
+        .map(|z| {
+            let mut v = z * l;
+            v.norm = Some(&v * &v);
+            v
+        })
+        .collect()
+}
+
+fn solve(input: &str, o: &Options) -> Result<Report, String> {
+    let format = o.format.unwrap_or(match &o.input {
+        Some(path) if path.ends_with(".json") => Format::Json,
+        _ => Format::Fplll,
+    });
+    let mut l = parse_basis(input, format)?;
+    let target = match &o.target {
+        Some(t) if t.len() != l.basis[0].vec.len() => {
+            return Err(format!(
+                "the target has {} entries but the basis vectors have {}",
+                t.len(),
+                l.basis[0].vec.len()
+            ));
+        }
+        Some(t) => Some(Vector {
+            vec: t.clone(),
+            norm: None,
+        }),
+        None => None,
+    };
+    let seed = o.seed.unwrap_or_else(|| rand::thread_rng().gen());
+    let mut rng = StdRng::seed_from_u64(seed);
+
+    let start = Instant::now();
+    reduce(&mut l, o);
+    let reduce_secs = start.elapsed().as_secs_f64();
+    if l.rank() != l.basis.len() {
+        return Err("the basis vectors are linearly dependent, reduce them with --reduce".into());
+    }
+    if l.basis.is_empty() {
+        return Err("the lattice has no nonzero vectors".into());
+    }
+
+    let stop = match o.stop {
+        StopArg::Stop(stop) => stop,
+        StopArg::Gh(r) => {
+            let gh = l.gaussian_heuristic(64).to_f64();
+            Stop::Norm((r * gh).powi(2))
+        }
+    };
+
+    let start = Instant::now();
+    let vectors = match (&target, o.solver) {
+        (Some(t), _) => vec![l.closest(t)],
+        (None, Solver::Gauss) => gauss(&l, o, &mut rng, stop),
+        (None, Solver::Gram) => gram(&l, o, &mut rng, stop),
+        (None, Solver::Enum) => vec![l.shortest()],
+    };
+    let solve_secs = start.elapsed().as_secs_f64();
+
+    // The distance to the target takes the place of the norm
+    let error = match (&target, vectors.first()) {
+        (Some(t), Some(v)) => {
+            let mut d = t - v;
+            d.norm = Some(&d * &d);
+            Some(d)
+        }
+        _ => None,
+    };
+
+    Ok(Report {
+        gh_ratio: error
+            .as_ref()
+            .or(vectors.first())
+            .map(|v| l.gh_ratio(v))
+            .unwrap_or(f64::NAN),
+        distance: error.and_then(|d| d.norm),
+        found: vectors.len(),
+        vectors: vectors.into_iter().take(o.count).collect(),
+        dim: (l.basis.len(), l.basis[0].vec.len()),
+        seed,
+        reduce_secs,
+        solve_secs,
+        root_hermite: l.root_hermite_factor(),
+    })
+}
+
+/// The statistics of a run as key-value pairs
+fn stats(r: &Report) -> Vec<(&'static str, String)> {
+    let mut stats = vec![
+        ("dimension", format!("{}x{}", r.dim.0, r.dim.1)),
+        ("seed", r.seed.to_string()),
+        ("reduce_secs", format!("{:.6}", r.reduce_secs)),
+        ("solve_secs", format!("{:.6}", r.solve_secs)),
+        ("found", r.found.to_string()),
+        (
+            "norm",
+            r.vectors
+                .first()
+                .and_then(|v| v.norm.as_ref())
+                .map(|n| n.to_string())
+                .unwrap_or_default(),
+        ),
+        ("gh_ratio", format!("{:.6}", r.gh_ratio)),
+        ("root_hermite_factor", format!("{:.6}", r.root_hermite)),
+    ];
+    if let Some(d) = &r.distance {
+        stats.insert(6, ("distance", d.to_string()));
+    }
+    stats
+}
+
+fn render_json(r: &Report) -> Result<String, String> {
+    let json = serde_json::json!({
+        "vectors": r.vectors,
+        "stats": {
+            "dimension": [r.dim.0, r.dim.1],
+            "seed": r.seed,
+            "reduce_secs": r.reduce_secs,
+            "solve_secs": r.solve_secs,
+            "found": r.found,
+            "norm": r.vectors.first().and_then(|v| v.norm.as_ref()).map(|n| n.to_string()),
+            "distance": r.distance.as_ref().map(|d| d.to_string()),
+            "gh_ratio": r.gh_ratio,
+            "root_hermite_factor": r.root_hermite,
+        },
+    });
+    serde_json::to_string_pretty(&json)
+        .map(|s| s + "\n")
+        .map_err(|e| e.to_string())
+}
+
+/// The shortest vectors in the output format
+fn render(r: &Report, format: Format) -> Result<String, String> {
+    let l = Lattice {
+        basis: r.vectors.clone(),
+    };
+    match format {
+        Format::Fplll => Ok(l.to_fplll()),
+        Format::Sage => Ok(l.to_sage() + "\n"),
+        Format::Json => render_json(r),
+    }
+}
+
+fn run(args: &[String]) -> Result<(), (i32, String)> {
+    let o = match parse_args(args).map_err(|e| (2, e))? {
+        Some(o) => o,
+        None => {
+            print!("{}", USAGE);
+            return Ok(());
+        }
+    };
+    let input = match o.input.as_deref() {
+        None | Some("-") => {
+            let mut s = String::new();
+            std::io::stdin()
+                .read_to_string(&mut s)
+                .map_err(|e| (1, e.to_string()))?;
+            s
+        }
+        Some(path) => std::fs::read_to_string(path).map_err(|e| (1, format!("{}: {}", path, e)))?,
+    };
+    let report = solve(&input, &o).map_err(|e| (1, e))?;
+    // Check the output format before printing any statistics
+    let out = render(&report, o.output).map_err(|e| (1, e))?;
+    for (k, v) in stats(&report) {
+        eprintln!("{}: {}", k, v);
+    }
+    print!("{}", out);
+    Ok(())
+}
+
+fn main() {
+    let args: Vec<String> = std::env::args().skip(1).collect();
+    if let Err((code, e)) = run(&args) {
+        eprintln!("svp: error: {}", e);
+        if code == 2 {
+            eprintln!("Try `svp --help` for more information.");
+        }
+        std::process::exit(code);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const DIM10: &str = "[[-1 0 1 0 1 0 0 0 -1 1]
+[-2 2 -1 0 2 3 0 1 0 -2]
+[-3 1 -1 1 0 -4 -1 -2 0 0]
+[1 6 0 0 1 0 2 0 0 2]
+[-2 1 -4 -1 -1 0 0 4 -3 2]
+[1 0 -5 -10 4 -3 -2 0 3 4]
+[5 0 -4 4 6 -6 0 4 -9 -7]
+[4 3 -2 -7 -2 3 0 -6 -12 -2]
+[1 6 0 1 -3 3 -15 3 -1 2]
+[0 3 11 -9 -5 -4 -3 8 -1 -7]
+]";
+
+    fn args(s: &str) -> Vec<String> {
+        s.split_whitespace().map(String::from).collect()
+    }
+
+    #[test]
+    fn test_args() {
+        let o = parse_args(&args("-r bkz -b 20 --seed=3 --stop gh:1.05 -n 5 basis.txt"))
+            .unwrap()
+            .unwrap();
+        assert_eq!(o.reduce, Reduce::Bkz);
+        assert_eq!((o.beta, o.seed, o.count), (20, Some(3), 5));
+        assert_eq!(o.stop, StopArg::Gh(1.05));
+        assert_eq!(o.input.as_deref(), Some("basis.txt"));
+        assert_eq!(parse_args(&args("-h")).unwrap(), None);
+        assert_eq!(
+            parse_stop("collisions:50").unwrap(),
+            StopArg::Stop(Stop::Collisions(50))
+        );
+
+        for bad in [
+            "--seed x",
+            "-s foo",
+            "-d 1.5",
+            "--stop norm",
+            "a b",
+            "--bogus 1",
+            "-b",
+        ] {
+            assert!(parse_args(&args(bad)).is_err(), "{}", bad);
+        }
+    }
+
+    #[test]
+    fn test_solve() {
+        for solver in ["gauss", "gram", "enum"] {
+            let o = parse_args(&args(&format!("-s {} --seed 1 -n 3", solver)))
+                .unwrap()
+                .unwrap();
+            let r = solve(DIM10, &o).unwrap();
+            assert_eq!(r.vectors[0].norm, Some(Integer::from(5)));
+            assert!(r.vectors.len() <= 3);
+            assert_eq!(r.dim, (10, 10));
+
+            // Every vector lies in the lattice
+            let l = Lattice::<Integer>::from_fplll(DIM10).unwrap();
+            assert!(l.coordinates_batch(&r.vectors).iter().all(|z| z.is_some()));
+        }
+
+        // Arbitrary precision, other reductions and a stop criterion
+        let o = parse_args(&args("-p 100 -r bkz -b 4 --seed 2 --stop gh:2"))
+            .unwrap()
+            .unwrap();
+        let r = solve(DIM10, &o).unwrap();
+        assert!(r.gh_ratio <= 2.0);
+        let out = render(&r, Format::Sage).unwrap();
+        assert!(out.starts_with("matrix(ZZ, [["));
+
+        // Generating sets need reducing first
+        let o = parse_args(&args("-r none")).unwrap().unwrap();
+        assert!(solve("[[1 2] [2 4] [0 1]]", &o).is_err());
+        assert!(solve("[[1 2] [3]]", &o).unwrap_err().contains("line 1"));
+
+        // The closest vector to a target, with its squared distance
+        let o = parse_args(&args("-r none --target [0,0,1]"));
+        assert!(o.is_err());
+        let o = parse_args(&["-t".into(), "[1 0 1347]".into()])
+            .unwrap()
+            .unwrap();
+        let r = solve("[[1 0 1345] [0 1 35] [0 0 2048]]", &o).unwrap();
+        assert_eq!(r.vectors[0].vec, vec![1, 0, 1345]);
+        assert_eq!(r.distance, Some(Integer::from(4)));
+        assert!(stats(&r).contains(&("distance", "4".into())));
+        assert!(solve("[[1 0] [0 1]]", &o).unwrap_err().contains("target"));
+
+        // Reduction removes the zero vector, leaving nothing to solve
+        let o = parse_args(&args("")).unwrap().unwrap();
+        assert!(solve("[[0 0]]", &o)
+            .unwrap_err()
+            .contains("no nonzero vectors"));
+    }
+
+    #[test]
+    fn test_json() {
+        let o = parse_args(&args("-f json -s enum")).unwrap().unwrap();
+        let r = solve(r#"[[1, 0, "1345"], [0, 1, 35], [0, 0, 2048]]"#, &o).unwrap();
+        let json = render(&r, Format::Json).unwrap();
+        let v: serde_json::Value = serde_json::from_str(&json).unwrap();
+        assert_eq!(v["stats"]["dimension"], serde_json::json!([3, 3]));
+        let b: Vec<Vector<Integer>> = serde_json::from_value(v["vectors"].clone()).unwrap();
+        assert_eq!(b[0].norm, r.vectors[0].norm);
+
+        // The crate's own serialization
+        let l = serde_json::to_string(&Lattice::<Integer>::from_fplll(DIM10).unwrap()).unwrap();
+        assert_eq!(
+            solve(&l, &o).unwrap().vectors[0].norm,
+            Some(Integer::from(5))
+        );
+    }
+}
@@ -0,0 +1,222 @@
+use super::state::Reduction;
+use crate::enumerate::Tree;
+use crate::{Field, Lattice, Ring, Vector};
+
+/**
+
+Implements BKZ reduction with Schnorr-Euchner enumeration \[SE94\]
+
+Tours run over the blocks b_k, .., b_{k+β-1} of an LLL-reduced basis. A
+shortest vector of the projected block π_k(L(b_k, .., b_{k+β-1})) is found
+by enumeration, and if it is shorter than δ·‖b*_k‖ it is inserted at
+position k, and the resulting dependency is removed by MLLL. Reduction
+stops once a whole tour leaves the basis unchanged. BKZ with β = 2 is LLL,
+and with β = n the first basis vector is a shortest vector of L(B).
+
+# Examples
+
+```rust
+use svp::{nvec, Lattice, Vector, BKZ};
+
+let mut l = Lattice {
+    basis: vec![
+        nvec![1i64, 0, 0, 1345],
+        nvec![0, 1, 0, 35],
+        nvec![0, 0, 1, 154],
+        nvec![0, 0, 0, 2048],
+    ],
+};
+l.bkz(4, 0.99);
+
+// b_0 is a shortest vector
+assert_eq!(l.basis[0].norm, Some(39));
+```
+**/
+pub trait BKZ<T> {
+    /// BKZ-reduce the basis in place with block size β ≥ 2 and LLL
+    /// parameter δ ∈ (1/4, 1), removing linear dependencies
+    fn bkz(&mut self, beta: usize, delta: f64);
+
+    /// Like `bkz`, but also return the transformation U as in `LLL::lll_transform`
+    fn bkz_transform(&mut self, beta: usize, delta: f64) -> Vec<Vector<T>>;
+}
+
+/// Enumerate a tree for the coefficients of a vector shorter than `radius`
+fn shortest_coefficients<F: Field>(mu: Vec<Vec<F>>, r: Vec<F>, radius: F) -> Option<Vec<F>> {
+    let mut radius = radius;
+    let mut tree = Tree::new(mu, r, radius.clone());
+    let mut best: Option<Vec<F>> = None;
+    tree.run(&mut |x, dist| {
+        if dist >= &radius {
+            return None;
+        }
+        radius = dist.clone();
+        best = Some(x.to_vec());
+        Some(dist.clone())
+    });
+    best
+}
+
+/// Enumerate the projected block b_k, .., b_{h-1} for coefficients of a
+/// vector shorter than δ·‖b*_k‖²
+pub(super) fn shortest_projected<T: Ring>(
+    r: &Reduction<T>,
+    k: usize,
+    h: usize,
+    delta: &T::Field,
+) -> Option<Vec<T>> {
+    let mu = (k..h)
+        .map(|i| (k..h).map(|j| r.c.mu[i][j].clone()).collect())
+        .collect();
+    let rs: Vec<T::Field> = r.c.r[k..h].to_vec();
+    let mut radius = r.c.r[k].clone();
+    radius *= delta;
+    let x = shortest_coefficients(mu, rs, radius)?;
+    Some(x.iter().map(coefficient).collect())
+}
+
+/// Enumerate the dual of the projected block b_k, .., b_{h-1} for a vector
+/// w shorter than δ / ‖b*_{h-1}‖², returned by its coordinates ⟨w, b_i⟩
+///
+/// The reversed dual of a block with GSO B = M·B* has the orthogonal vectors
+/// b*_{h-1} / ‖b*_{h-1}‖², .., b*_k / ‖b*_k‖² and coefficients J·M⁻ᵀ·J.
+pub(super) fn shortest_dual_projected<T: Ring>(
+    r: &Reduction<T>,
+    k: usize,
+    h: usize,
+    delta: &T::Field,
+) -> Option<Vec<T>> {
+    let prec = r.prec();
+    let b = h - k;
+
+    // M⁻¹ by forward substitution, M being unit lower triangular
+    let mut inv = vec![vec![T::Field::zero(prec); b]; b];
+    for i in 0..b {
+        inv[i][i] = T::Field::from_f64(1.0, prec);
+        for j in 0..i {
+            let mut x = T::Field::zero(prec);
+            for l in j..i {
+                let mut tmp = r.c.mu[k + i][k + l].clone();
+                tmp *= &inv[l][j];
+                x -= &tmp;
+            }
+            inv[i][j] = x;
+        }
+    }
+
+    let mu = (0..b)
+        .map(|i| (0..b).map(|j| inv[b - 1 - j][b - 1 - i].clone()).collect())
+        .collect();
+    let rs = (0..b)
+        .map(|i| {
+            let mut x = T::Field::from_f64(1.0, prec);
+            x /= &r.c.r[h - 1 - i];
+            x
+        })
+        .collect();
+    let mut radius = delta.clone();
+    radius /= &r.c.r[h - 1];
+    let y = shortest_coefficients(mu, rs, radius)?;
+    Some(y.iter().rev().map(coefficient).collect())
+}
+
+/// Round an enumerated coefficient, which is bounded by the search radius
+fn coefficient<T: Ring>(x: &T::Field) -> T {
+    T::from_field(x).expect("machine integer overflow")
+}
+
+/// Run BKZ, optionally tracking the transformation
+fn run<T: Ring>(l: &mut Lattice<T>, beta: usize, delta: f64, track: bool) -> Vec<Vector<T>> {
+    assert!(beta >= 2, "block size must be at least 2");
+    let mut r = Reduction::new(l, track, 1);
+    let n = r.b.len();
+    if n == 0 {
+        return r.finish();
+    }
+    let delta = T::Field::from_f64(delta, r.prec());
+    let n = r.lll(&delta, 0, n);
+    let relations = r.relations();
+
+    // Number of consecutive blocks left unchanged
+    let mut clean = 0;
+    let mut k = 0;
+    while clean + 1 < n {
+        let h = (k + beta).min(n);
+        match shortest_projected(&r, k, h, &delta) {
+            Some(x) => {
+                r.insert(k, &x);
+                r.lll(&delta, k, n + 1);
+                r.truncate_relations(relations);
+                clean = 0;
+            }
+            None => clean += 1,
+        }
+        k = (k + 1) % (n - 1);
+    }
+    r.finish()
+}
+
+impl<T: Ring> BKZ<T> for Lattice<T> {
+    fn bkz(&mut self, beta: usize, delta: f64) {
+        run(self, beta, delta, false);
+    }
+
+    fn bkz_transform(&mut self, beta: usize, delta: f64) -> Vec<Vector<T>> {
+        run(self, beta, delta, true)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::testing;
+    use crate::*;
+
+    #[test]
+    fn test_prim() {
+        let l = testing::dim10();
+        for beta in [2, 5, 10] {
+            let mut r = l.clone();
+            let u = r.bkz_transform(beta, 0.99);
+            assert_eq!(r.basis.len(), 10);
+            testing::assert_transform(&l, &r, &u);
+        }
+
+        // Full enumeration finds a shortest vector
+        let mut r = l.clone();
+        r.bkz(10, 0.99);
+        assert_eq!(r.basis[0].norm, Some(5));
+
+        // A stronger reduction has a smaller potential
+        let (mut lll, mut bkz) = (l.clone(), l.clone());
+        lll.lll(0.99);
+        bkz.bkz(6, 0.99);
+        assert!(bkz.profile().log_potential <= lll.profile().log_potential + 1e-9);
+    }
+
+    #[test]
+    #[should_panic(expected = "block size must be at least 2")]
+    fn test_block_size() {
+        let mut l = Lattice {
+            basis: vec![nvec![1i64, 0], nvec![0, 1]],
+        };
+        l.bkz(0, 0.99);
+    }
+
+    #[test]
+    fn test_generating_set() {
+        let mut l = testing::generating_set();
+        let g = l.clone();
+        let u = l.bkz_transform(3, 0.99);
+        assert_eq!(l.basis.len(), 3);
+        testing::assert_transform(&g, &l, &u);
+    }
+
+    #[test]
+    fn test_mp() {
+        let mut l = testing::knapsack4();
+        let b = l.clone();
+        l.bkz(4, 0.99);
+        assert!(l.same_lattice(&b));
+        assert_eq!(l.basis[0].norm, b.shortest().norm);
+    }
+}
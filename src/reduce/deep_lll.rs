@@ -0,0 +1,171 @@
+use super::state::{is_zero, Reduction};
+use crate::{Field, Lattice, Ring, Vector};
+
+/**
+
+Implements LLL with deep insertions \[SE94\]
+
+Instead of swapping b_k with b_{k-1} only, b_k is inserted at the first
+position i < k where its projection is short, i.e. where
+‖π_i(b_k)‖² < δ·‖b*_i‖². Deep insertions are tried at positions i < depth
+and k - i ≤ depth, and at all positions for a depth of 0. The output is
+LLL-reduced and in practice close to BKZ-10, which makes it a cheap
+preprocessing step for sieving.
+
+# Examples
+
+```rust
+use rand::SeedableRng;
+use rand_chacha::ChaCha20Rng;
+use svp::{DeepLLL, Lattice, QualityProfile, LLL};
+
+let l = Lattice::knapsack(16, 40, &mut ChaCha20Rng::seed_from_u64(1));
+let (mut lll, mut deep) = (l.clone(), l.clone());
+lll.lll(0.99);
+
+// Deep insertions at all positions
+deep.deep_lll(0.99, 0);
+assert!(deep.profile().log_potential < lll.profile().log_potential);
+
+// Only at the first and last 4 positions
+let mut d4 = l.clone();
+d4.deep_lll(0.99, 4);
+assert!(d4.profile().log_potential < lll.profile().log_potential);
+```
+**/
+pub trait DeepLLL<T> {
+    /// Reduce the basis in place with parameter δ ∈ (1/4, 1) and insertion
+    /// depth `depth`, removing linear dependencies
+    fn deep_lll(&mut self, delta: f64, depth: usize);
+
+    /// Like `deep_lll`, but also return the transformation U as in `LLL::lll_transform`
+    fn deep_lll_transform(&mut self, delta: f64, depth: usize) -> Vec<Vector<T>>;
+}
+
+/// Run DeepLLL, optionally tracking the transformation
+fn run<T: Ring>(l: &mut Lattice<T>, delta: f64, depth: usize, track: bool) -> Vec<Vector<T>> {
+    let mut r = Reduction::new(l, track, 0);
+    if r.b.is_empty() {
+        return r.finish();
+    }
+    let prec = r.prec();
+    let delta = T::Field::from_f64(delta, prec);
+    r.c.update(r.b, 0);
+
+    let mut k = 1;
+    while k < r.b.len() {
+        r.size_reduce(k);
+        if is_zero(&r.b[k]) {
+            r.remove(k);
+            continue;
+        }
+
+        // ‖π_i(b_k)‖², starting from ‖b_k‖²
+        let mut c = (&r.b[k] * &r.b[k]).to_field(prec);
+        let mut pos = None;
+        for i in 0..k {
+            let mut bound = delta.clone();
+            bound *= &r.c.r[i];
+            let allowed = depth == 0 || i < depth || k - i <= depth;
+            if allowed && c < bound {
+                pos = Some(i);
+                break;
+            }
+            let mut tmp = r.c.mu[k][i].clone();
+            tmp *= &r.c.mu[k][i];
+            tmp *= &r.c.r[i];
+            c -= &tmp;
+        }
+
+        match pos {
+            Some(i) => {
+                r.rotate(i, k);
+                if i == 0 {
+                    r.c.update(r.b, 0);
+                }
+                k = i.max(1);
+            }
+            None => k += 1,
+        }
+    }
+    r.finish()
+}
+
+impl<T: Ring> DeepLLL<T> for Lattice<T> {
+    fn deep_lll(&mut self, delta: f64, depth: usize) {
+        run(self, delta, depth, false);
+    }
+
+    fn deep_lll_transform(&mut self, delta: f64, depth: usize) -> Vec<Vector<T>> {
+        run(self, delta, depth, true)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::testing;
+    use crate::*;
+
+    /// Test ‖π_i(b_k)‖² ≥ δ·‖b*_i‖² for all i < k
+    fn is_deep_reduced(l: &Lattice<i64>, delta: f64) -> bool {
+        let gs: Vec<Vector<f64>> = l.gso();
+        let b: Vec<Vector<f64>> = l
+            .basis
+            .iter()
+            .map(|v| Vector {
+                vec: v.vec.iter().map(|x| *x as f64).collect(),
+                norm: None,
+            })
+            .collect();
+        for k in 0..b.len() {
+            let mut c = &b[k] * &b[k];
+            for i in 0..k {
+                let r = gs[i].norm.unwrap();
+                if c < delta * r - 1e-9 {
+                    return false;
+                }
+                let mu = (&b[k] * &gs[i]) / r;
+                c -= mu * mu * r;
+            }
+        }
+        true
+    }
+
+    #[test]
+    fn test_prim() {
+        let l = testing::dim10();
+        for depth in [0, 1, 3] {
+            let mut r = l.clone();
+            let u = r.deep_lll_transform(0.99, depth);
+            testing::assert_transform(&l, &r, &u);
+        }
+
+        let mut r = l.clone();
+        r.deep_lll(0.99, 0);
+        assert!(is_deep_reduced(&r, 0.99));
+    }
+
+    #[test]
+    fn test_generating_set() {
+        let mut l: Lattice<i64> = Lattice {
+            basis: vec![
+                nvec![1, 2, 3],
+                nvec![2, 4, 6],
+                nvec![3, 7, 10],
+                nvec![0, 1, 1],
+            ],
+        };
+        l.deep_lll(0.99, 2);
+        assert_eq!(l.basis.len(), 2);
+        assert!(is_deep_reduced(&l, 0.99));
+    }
+
+    #[test]
+    fn test_mp() {
+        let mut l = testing::parallel_mp();
+        let b = l.clone();
+        l.deep_lll(0.99, 0);
+        assert!(l.same_lattice(&b));
+        assert!(l.basis[0].norm.as_ref().unwrap() < &100);
+    }
+}
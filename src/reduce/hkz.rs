@@ -0,0 +1,101 @@
+use super::bkz::shortest_projected;
+use super::state::Reduction;
+use crate::{Field, Lattice, Ring, Vector};
+
+/**
+
+Implements Hermite-Korkine-Zolotarev (HKZ) reduction
+
+Each b_k is replaced by a shortest vector of the projected lattice
+π_k(L), found by Schnorr-Euchner enumeration, and the basis is size
+reduced. In particular b_0 is a shortest vector of L(B). The running time
+is that of n enumerations, so the basis should be small or well reduced.
+
+# Examples
+
+```rust
+use rand::SeedableRng;
+use rand_chacha::ChaCha20Rng;
+use svp::{Enumerate, ExactGSO, Lattice, HKZ};
+
+let mut l = Lattice::knapsack(8, 20, &mut ChaCha20Rng::seed_from_u64(1));
+let v = l.shortest();
+l.hkz();
+assert_eq!(l.basis[0].norm, v.norm);
+
+// Every ‖b*_k‖ = λ₁(π_k(L)) is left unchanged by a second pass
+let d = l.exact_gso().d;
+l.hkz();
+assert_eq!(l.exact_gso().d, d);
+```
+**/
+pub trait HKZ<T> {
+    /// HKZ-reduce the basis in place, removing linear dependencies
+    fn hkz(&mut self);
+
+    /// Like `hkz`, but also return the transformation U as in `LLL::lll_transform`
+    fn hkz_transform(&mut self) -> Vec<Vector<T>>;
+}
+
+/// Run HKZ reduction, optionally tracking the transformation
+fn run<T: Ring>(l: &mut Lattice<T>, track: bool) -> Vec<Vector<T>> {
+    let mut r = Reduction::new(l, track, 1);
+    let n = r.b.len();
+    if n == 0 {
+        return r.finish();
+    }
+    let delta = T::Field::from_f64(0.99, r.prec());
+    let one = T::Field::from_f64(1.0, r.prec());
+    let n = r.lll(&delta, 0, n);
+    let relations = r.relations();
+
+    for k in 0..n - 1 {
+        if let Some(x) = shortest_projected(&r, k, n, &one) {
+            r.insert(k, &x);
+            r.lll(&delta, k, n + 1);
+            r.truncate_relations(relations);
+        }
+    }
+    r.finish()
+}
+
+impl<T: Ring> HKZ<T> for Lattice<T> {
+    fn hkz(&mut self) {
+        run(self, false);
+    }
+
+    fn hkz_transform(&mut self) -> Vec<Vector<T>> {
+        run(self, true)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::testing;
+    use crate::*;
+
+    #[test]
+    fn test_prim() {
+        let l = testing::dim10();
+        let mut r = l.clone();
+        let u = r.hkz_transform();
+        testing::assert_transform(&l, &r, &u);
+        assert_eq!(r.basis[0].norm, Some(5));
+
+        // b*_k is a shortest vector of π_k(L), so ‖b*_k‖² = λ₁(π_k(L))² is
+        // left unchanged by a second pass
+        let gs = r.exact_gso();
+        let mut s = r.clone();
+        s.hkz();
+        assert_eq!(s.exact_gso().d, gs.d);
+    }
+
+    #[test]
+    fn test_mp() {
+        let mut l = testing::knapsack4();
+        let b = l.clone();
+        l.hkz();
+        assert!(l.same_lattice(&b));
+        assert_eq!(l.basis[0].norm, l.shortest().norm);
+    }
+}
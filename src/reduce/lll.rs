@@ -0,0 +1,173 @@
+use super::state::Reduction;
+use crate::{Field, Lattice, Ring, Vector};
+
+/**
+
+Implements LLL reduction \[LLL82\] of generating sets in the style of MLLL \[Poh87\]
+
+The Gram-Schmidt coefficients are recomputed in floating point from exact
+inner products of the basis vectors \[SE94\]. Generating sets may be
+linearly dependent: every dependency eventually reduces some vector to
+zero, which is then removed, so the reduced basis has as many vectors as
+the rank of the input.
+
+# Examples
+
+```rust
+use svp::{nvec, Lattice, Vector, LLL};
+
+// A 2x3 basis
+let mut l = Lattice {
+    basis: vec![nvec![1i64, 2, 3], nvec![4, 5, 6]],
+};
+assert_eq!(l.lll(0.99), 2);
+assert_eq!(l.basis[0].norm, Some(5));
+
+// Linearly dependent generating sets are reduced to a basis
+let mut l = Lattice {
+    basis: vec![
+        nvec![1i64, 2, 3],
+        nvec![2, 4, 6],
+        nvec![3, 7, 10],
+        nvec![0, 1, 1],
+    ],
+};
+let g = l.clone();
+let u = l.lll_transform(0.99);
+assert_eq!(l.basis.len(), 2);
+
+// B' = U·B, followed by the relations between the generators
+for (i, ui) in u.iter().enumerate() {
+    let v = ui * &g;
+    match i < 2 {
+        true => assert_eq!(v.vec, l.basis[i].vec),
+        false => assert_eq!(v.vec, vec![0, 0, 0]),
+    }
+}
+```
+**/
+pub trait LLL<T> {
+    /// LLL-reduce the basis in place with parameter δ ∈ (1/4, 1), removing
+    /// linear dependencies, and return the rank of the lattice
+    fn lll(&mut self, delta: f64) -> usize;
+
+    /// Like `lll`, but also return the unimodular n×n matrix U such that
+    /// the first rows of U·B are the reduced basis and the remaining rows
+    /// are integer relations between the original vectors
+    fn lll_transform(&mut self, delta: f64) -> Vec<Vector<T>>;
+}
+
+/// Run LLL, optionally tracking the transformation
+fn run<T: Ring>(l: &mut Lattice<T>, delta: f64, track: bool) -> Vec<Vector<T>> {
+    let mut r = Reduction::new(l, track, 0);
+    let n = r.b.len();
+    if n > 0 {
+        let delta = T::Field::from_f64(delta, r.prec());
+        r.lll(&delta, 0, n);
+    }
+    r.finish()
+}
+
+impl<T: Ring> LLL<T> for Lattice<T> {
+    fn lll(&mut self, delta: f64) -> usize {
+        run(self, delta, false);
+        self.basis.len()
+    }
+
+    fn lll_transform(&mut self, delta: f64) -> Vec<Vector<T>> {
+        run(self, delta, true)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::testing;
+    use crate::*;
+    use rug::Integer;
+
+    /// Check size reduction and the Lovász condition with exact arithmetic
+    fn is_reduced(l: &Lattice<Integer>, delta: f64) -> bool {
+        let gs = l.exact_gso();
+        let n = l.basis.len();
+        let d = |i: usize| rug::Rational::from(gs.d[i].clone());
+        for i in 0..n {
+            for j in 0..i {
+                // μ_{i,j} = λ_{i,j} / d_{j+1}
+                let mu = rug::Rational::from((gs.lambda[i][j].clone(), gs.d[j + 1].clone()));
+                if mu.abs() > rug::Rational::from((51, 100)) {
+                    return false;
+                }
+            }
+        }
+        for k in 1..n {
+            // ‖b*_k‖² = d_{k+1} / d_k
+            let rk = d(k + 1) / d(k);
+            let rk1 = d(k) / d(k - 1);
+            let mu = rug::Rational::from((gs.lambda[k][k - 1].clone(), gs.d[k].clone()));
+            let bound = (rug::Rational::from_f64(delta).unwrap() - mu.clone() * mu) * rk1;
+            if rk < bound {
+                return false;
+            }
+        }
+        true
+    }
+
+    #[test]
+    fn test_prim() {
+        let l = testing::dim10();
+        let mut r = l.clone();
+        assert_eq!(r.lll(0.99), 10);
+
+        let (l, r) = (Lattice::<Integer>::from(&l), Lattice::<Integer>::from(&r));
+        assert!(r.same_lattice(&l));
+        assert!(is_reduced(&r, 0.99));
+        assert_eq!(r.shortest().norm, Some(Integer::from(5)));
+    }
+
+    #[test]
+    fn test_rank_deficient() {
+        // Rows of a 6x5 matrix of rank 3, including a zero row
+        let mut l: Lattice<i64> = Lattice {
+            basis: vec![
+                nvec![2, 0, 1, 4, 3],
+                nvec![0, 0, 0, 0, 0],
+                nvec![1, 1, 0, 2, 1],
+                nvec![3, 1, 1, 6, 4],
+                nvec![7, 3, 2, 14, 9],
+                nvec![0, 5, -1, 1, 2],
+            ],
+        };
+        let g = Lattice::<Integer>::from(&l);
+        let b = l.clone();
+        assert_eq!(l.rank(), 3);
+        let u = l.lll_transform(0.75);
+        assert_eq!(l.basis.len(), 3);
+        assert_eq!(l.rank(), 3);
+
+        // U is unimodular, its last rows are relations
+        testing::assert_transform(&b, &l, &u);
+
+        let r = Lattice::<Integer>::from(&l);
+        assert!(r.same_lattice(&g));
+        assert!(is_reduced(&r, 0.75));
+        for v in &l.basis {
+            assert_eq!(v.norm, Some(v * v));
+        }
+
+        // The GSO of a generating set has vanishing vectors
+        let gs = g.gso();
+        assert_eq!(gs[1].norm.as_ref().unwrap(), &0);
+    }
+
+    #[test]
+    fn test_mp() {
+        // Nearly parallel vectors with 200-bit entries
+        let mut l = testing::parallel_mp();
+        let g = l.clone();
+        let u = l.lll_transform(0.99);
+        assert!(l.same_lattice(&g));
+        testing::assert_transform(&g, &l, &u);
+        assert!(is_reduced(&l, 0.99));
+        assert!(l.basis[0].norm.as_ref().unwrap() < &100);
+    }
+}
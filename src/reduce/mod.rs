@@ -0,0 +1,18 @@
+mod bkz;
+mod deep_lll;
+mod hkz;
+mod lll;
+mod pot_lll;
+mod sd_bkz;
+mod size;
+mod slide;
+mod state;
+
+pub use bkz::BKZ;
+pub use deep_lll::DeepLLL;
+pub use hkz::HKZ;
+pub use lll::LLL;
+pub use pot_lll::PotLLL;
+pub use sd_bkz::SelfDualBKZ;
+pub use size::SizeReduce;
+pub use slide::Slide;
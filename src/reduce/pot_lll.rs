@@ -0,0 +1,174 @@
+use super::state::{is_zero, Reduction};
+use crate::{Field, Lattice, Ring, Vector};
+
+/**
+
+Implements Potential-LLL \[FSW14\]
+
+A deep insertion of b_k at position i < k is performed whenever it
+decreases the potential Π ‖b*_i‖^(2(n - i)) of the basis by a factor of
+at least δ. The potential ratio of moving b_k to position i is
+Π_{j=i}^{k-1} ‖π_j(b_k)‖² / ‖b*_j‖², and the insertion with the smallest
+ratio is chosen. Unlike DeepLLL, it runs in polynomial time, and its output
+is LLL-reduced.
+
+# Examples
+
+```rust
+use svp::{nvec, Lattice, PotLLL, QualityProfile, Vector, LLL};
+
+let b = Lattice {
+    basis: vec![
+        nvec![1i64, 0, 0, 0, 21034],
+        nvec![0, 1, 0, 0, 1093],
+        nvec![0, 0, 1, 0, 9980],
+        nvec![0, 0, 0, 1, 13573],
+        nvec![0, 0, 0, 0, 32749],
+    ],
+};
+
+let (mut pot, mut lll) = (b.clone(), b.clone());
+pot.pot_lll(0.99);
+lll.lll(0.99);
+assert!(pot.profile().log_potential <= lll.profile().log_potential);
+```
+**/
+pub trait PotLLL<T> {
+    /// Reduce the basis in place with parameter δ ∈ (1/4, 1), removing
+    /// linear dependencies
+    fn pot_lll(&mut self, delta: f64);
+
+    /// Like `pot_lll`, but also return the transformation U as in `LLL::lll_transform`
+    fn pot_lll_transform(&mut self, delta: f64) -> Vec<Vector<T>>;
+}
+
+/// Run Potential-LLL, optionally tracking the transformation
+fn run<T: Ring>(l: &mut Lattice<T>, delta: f64, track: bool) -> Vec<Vector<T>> {
+    let mut r = Reduction::new(l, track, 0);
+    if r.b.is_empty() {
+        return r.finish();
+    }
+    let prec = r.prec();
+    let delta = T::Field::from_f64(delta, prec);
+    let one = T::Field::from_f64(1.0, prec);
+    r.c.update(r.b, 0);
+
+    let mut k = 1;
+    while k < r.b.len() {
+        r.size_reduce(k);
+        if is_zero(&r.b[k]) {
+            r.remove(k);
+            continue;
+        }
+
+        // Potential ratio of inserting b_k at i, for i = k - 1 down to 0
+        let mut c = r.c.r[k].clone();
+        let mut ratio = one.clone();
+        let (mut best, mut pos) = (one.clone(), k);
+        for i in (0..k).rev() {
+            let mut tmp = r.c.mu[k][i].clone();
+            tmp *= &r.c.mu[k][i];
+            tmp *= &r.c.r[i];
+            c += &tmp;
+            ratio *= &c;
+            ratio /= &r.c.r[i];
+            if ratio < best {
+                best = ratio.clone();
+                pos = i;
+            }
+        }
+
+        if best < delta {
+            r.rotate(pos, k);
+            if pos == 0 {
+                r.c.update(r.b, 0);
+            }
+            k = pos.max(1);
+        } else {
+            k += 1;
+        }
+    }
+    r.finish()
+}
+
+impl<T: Ring> PotLLL<T> for Lattice<T> {
+    fn pot_lll(&mut self, delta: f64) {
+        run(self, delta, false);
+    }
+
+    fn pot_lll_transform(&mut self, delta: f64) -> Vec<Vector<T>> {
+        run(self, delta, true)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::testing;
+    use crate::*;
+
+    /// Test that no insertion decreases the potential by a factor below δ
+    fn is_pot_reduced(l: &Lattice<i64>, delta: f64) -> bool {
+        let gs: Vec<Vector<f64>> = l.gso();
+        for k in 0..l.basis.len() {
+            let bk = Vector {
+                vec: l.basis[k].vec.iter().map(|x| *x as f64).collect(),
+                norm: None,
+            };
+            let mut c = gs[k].norm.unwrap();
+            let mut ratio = 1.0;
+            for i in (0..k).rev() {
+                let r = gs[i].norm.unwrap();
+                let mu = (&bk * &gs[i]) / r;
+                c += mu * mu * r;
+                ratio *= c / r;
+                if ratio < delta - 1e-9 {
+                    return false;
+                }
+            }
+        }
+        true
+    }
+
+    #[test]
+    fn test_prim() {
+        let l = testing::dim10();
+        let mut r = l.clone();
+        let u = r.pot_lll_transform(0.99);
+        testing::assert_transform(&l, &r, &u);
+        assert!(is_pot_reduced(&r, 0.99));
+
+        // Potential-LLL reduced bases are LLL reduced, LLL leaves them as is
+        let mut s = r.clone();
+        let u = s.lll_transform(0.99);
+        for (i, ui) in u.iter().enumerate() {
+            assert!(ui
+                .vec
+                .iter()
+                .enumerate()
+                .all(|(j, x)| *x == (i == j) as i64));
+        }
+    }
+
+    #[test]
+    fn test_generating_set() {
+        let mut l: Lattice<i64> = Lattice {
+            basis: vec![
+                nvec![1, 2, 3],
+                nvec![2, 4, 6],
+                nvec![3, 7, 10],
+                nvec![0, 1, 1],
+            ],
+        };
+        l.pot_lll(0.99);
+        assert_eq!(l.basis.len(), 2);
+        assert!(is_pot_reduced(&l, 0.99));
+    }
+
+    #[test]
+    fn test_mp() {
+        let mut l = testing::parallel_mp();
+        let b = l.clone();
+        l.pot_lll(0.99);
+        assert!(l.same_lattice(&b));
+    }
+}
@@ -0,0 +1,169 @@
+use super::bkz::{shortest_dual_projected, shortest_projected};
+use super::state::Reduction;
+use crate::{Field, Lattice, Ring, Vector};
+
+/**
+
+Implements self-dual BKZ \[MW16\]
+
+Each tour first runs forward over the primal blocks b_k, .., b_{k+β-1},
+inserting a shortest vector of every projected block as BKZ does, then
+backward over the blocks b_{h-β}, .., b_{h-1}, maximizing ‖b*_{h-1}‖ with
+a shortest vector of the dual block. Tours repeat until neither kind of
+step improves a block by a factor δ, or a tour fails to lower the
+potential Π ‖b*_i‖^(2(n - i)) by a factor √δ. Like slide reduction, the
+output has a provable Hermite factor, and it behaves like BKZ in practice.
+The output is LLL-reduced.
+
+# Examples
+
+```rust
+use rand::SeedableRng;
+use rand_chacha::ChaCha20Rng;
+use svp::{Enumerate, Lattice, SelfDualBKZ, LLL};
+
+let mut l = Lattice::goldstein_mayer(10, 20, &mut ChaCha20Rng::seed_from_u64(1));
+l.lll(0.99);
+let v = l.shortest();
+
+// The full block is solved exactly
+l.sd_bkz(10, 0.99);
+assert_eq!(l.basis[0].norm, v.norm);
+```
+**/
+pub trait SelfDualBKZ<T> {
+    /// Reduce the basis in place with block size β ≥ 2 and parameter
+    /// δ ∈ (1/4, 1), removing linear dependencies
+    fn sd_bkz(&mut self, beta: usize, delta: f64);
+
+    /// Like `sd_bkz`, but also return the transformation U as in `LLL::lll_transform`
+    fn sd_bkz_transform(&mut self, beta: usize, delta: f64) -> Vec<Vector<T>>;
+}
+
+/// ln(Π ‖b*_i‖^(2(n - i))), the log of the basis potential
+fn log_potential<T: Ring>(r: &Reduction<T>, n: usize) -> T::Field {
+    let mut p = T::Field::zero(r.prec());
+    for (i, x) in r.c.r[..n].iter().enumerate() {
+        let mut x = x.ln();
+        x *= &T::Field::from_f64((n - i) as f64, r.prec());
+        p += &x;
+    }
+    p
+}
+
+/// Run self-dual BKZ, optionally tracking the transformation
+fn run<T: Ring>(l: &mut Lattice<T>, beta: usize, delta: f64, track: bool) -> Vec<Vector<T>> {
+    assert!(beta >= 2, "block size must be at least 2");
+    let mut r = Reduction::new(l, track, 1);
+    let n = r.b.len();
+    if n == 0 {
+        return r.finish();
+    }
+    let delta = T::Field::from_f64(delta, r.prec());
+    let n = r.lll(&delta, 0, n);
+    let relations = r.relations();
+    let beta = beta.min(n);
+
+    // Dual steps lower the potential but primal steps may raise it, so tours
+    // can cycle; stop once a tour no longer lowers it by a factor √δ
+    let mut min_gain = delta.ln();
+    min_gain /= &T::Field::from_f64(2.0, r.prec());
+    let mut potential = log_potential(&r, n);
+
+    let mut changed = n > 1;
+    while changed {
+        changed = false;
+
+        // Forward tour over the primal blocks
+        for k in 0..=n - beta {
+            if let Some(x) = shortest_projected(&r, k, k + beta, &delta) {
+                r.insert(k, &x);
+                r.lll(&delta, k, n + 1);
+                r.truncate_relations(relations);
+                changed = true;
+            }
+        }
+
+        // Backward tour over the dual blocks
+        for h in (beta..=n).rev() {
+            if let Some(x) = shortest_dual_projected(&r, h - beta, h, &delta) {
+                r.insert_dual(h - beta, h, &x, &delta);
+                changed = true;
+            }
+        }
+
+        let mut bound = potential;
+        bound += &min_gain;
+        potential = log_potential(&r, n);
+        if potential > bound {
+            break;
+        }
+    }
+    r.lll(&delta, 0, n);
+    r.finish()
+}
+
+impl<T: Ring> SelfDualBKZ<T> for Lattice<T> {
+    fn sd_bkz(&mut self, beta: usize, delta: f64) {
+        run(self, beta, delta, false);
+    }
+
+    fn sd_bkz_transform(&mut self, beta: usize, delta: f64) -> Vec<Vector<T>> {
+        run(self, beta, delta, true)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::testing;
+    use crate::*;
+    use rand::SeedableRng;
+    use rand_chacha::ChaCha20Rng;
+    use rug::Rational;
+
+    #[test]
+    fn test_prim() {
+        let l = testing::dim10();
+        for beta in [2, 4, 6] {
+            let mut r = l.clone();
+            let u = r.sd_bkz_transform(beta, 0.99);
+            assert_eq!(r.basis.len(), 10);
+            testing::assert_transform(&l, &r, &u);
+        }
+
+        // The full block is solved exactly, in the primal and in the dual:
+        // ‖b*_{n-1}‖² ≥ δ / λ₁(L*)²
+        let mut r = l.clone();
+        r.sd_bkz(10, 0.99);
+        assert_eq!(r.basis[0].norm, Some(5));
+        let gs = r.exact_gso();
+        let last = Rational::from((gs.d[10].clone(), gs.d[9].clone()));
+        let d = r.dual();
+        let dual = Rational::from((d.basis.shortest().norm.unwrap(), d.scale.square()));
+        let x = last * dual;
+        assert!(x <= 1 && x >= Rational::from((99, 100)));
+    }
+
+    #[test]
+    fn test_mp() {
+        let mut l = testing::knapsack4();
+        let b = l.clone();
+        let u = l.sd_bkz_transform(2, 0.99);
+        assert!(l.same_lattice(&b));
+        testing::assert_transform(&b, &l, &u);
+    }
+
+    #[test]
+    fn test_termination() {
+        // Primal steps can raise the potential, and these used to cycle
+        for seed in 0..4 {
+            let l = Lattice::goldstein_mayer(14, 140, &mut ChaCha20Rng::seed_from_u64(seed));
+            for beta in [3, 4, 5, 7] {
+                let mut r = l.clone();
+                r.sd_bkz(beta, 0.99);
+                assert_eq!(r.basis.len(), 14);
+                assert!(r.same_lattice(&l));
+            }
+        }
+    }
+}
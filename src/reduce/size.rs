@@ -0,0 +1,104 @@
+use super::state::Reduction;
+use crate::{Lattice, Ring, Vector};
+
+/**
+
+Size reduction of a basis, so that |μ_{i,j}| ≤ 0.51 for all j < i
+
+Each b_i is reduced against b_0, .., b_{i-1} in turn. Size reduction leaves
+the Gram-Schmidt vectors unchanged, it only shortens the basis vectors and
+is the first step of every reduction algorithm. The basis vectors must be
+linearly independent.
+
+# Examples
+
+```rust
+use svp::{nvec, Lattice, SizeReduce, Vector};
+
+let mut l = Lattice {
+    basis: vec![nvec![1i64, 1, 0], nvec![7, 9, 0], nvec![9, 11, 2]],
+};
+let b = l.clone();
+let u = l.size_reduce_transform();
+assert_eq!(l.basis[1].vec, vec![-1, 1, 0]);
+assert_eq!(l.basis[2].vec, vec![0, 0, 2]);
+
+// B' = U·B
+for (ui, v) in u.iter().zip(&l.basis) {
+    assert_eq!((ui * &b).vec, v.vec);
+}
+```
+**/
+pub trait SizeReduce<T> {
+    fn size_reduce(&mut self);
+
+    /// Like `size_reduce`, but also return the unimodular U with B' = U·B
+    fn size_reduce_transform(&mut self) -> Vec<Vector<T>>;
+}
+
+/// Run size reduction, optionally tracking the transformation
+fn run<T: Ring>(l: &mut Lattice<T>, track: bool) -> Vec<Vector<T>> {
+    let mut r = Reduction::new(l, track, 0);
+    if !r.b.is_empty() {
+        r.c.update(r.b, 0);
+    }
+    for k in 1..r.b.len() {
+        r.size_reduce(k);
+    }
+    r.finish()
+}
+
+impl<T: Ring> SizeReduce<T> for Lattice<T> {
+    fn size_reduce(&mut self) {
+        run(self, false);
+    }
+
+    fn size_reduce_transform(&mut self) -> Vec<Vector<T>> {
+        run(self, true)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::testing;
+    use crate::*;
+    use rug::{Integer, Rational};
+
+    #[test]
+    fn test_prim() {
+        let l = testing::dim10();
+        let mut r = l.clone();
+        let u = r.size_reduce_transform();
+        testing::assert_transform(&l, &r, &u);
+        for v in &r.basis {
+            assert_eq!(v.norm, Some(v * v));
+        }
+
+        // The Gram-Schmidt vectors are unchanged
+        let (gl, gr) = (l.exact_gso(), r.exact_gso());
+        assert_eq!(gl.d, gr.d);
+        for i in 0..10 {
+            for j in 0..i {
+                let mu = Rational::from((gr.lambda[i][j].clone(), gr.d[j + 1].clone()));
+                assert!(mu.abs() <= Rational::from((51, 100)));
+            }
+        }
+    }
+
+    #[test]
+    fn test_mp() {
+        let big: Integer = Integer::from(1) << 300;
+        let mut l = Lattice {
+            basis: vec![
+                nvec![Integer::from(1), Integer::from(2), Integer::new()],
+                nvec![big.clone() + 3, Integer::from(2) * &big + 1, Integer::new()],
+                nvec![Integer::from(5), big.clone(), Integer::from(7)],
+            ],
+        };
+        let b = l.clone();
+        l.size_reduce();
+        assert!(l.same_lattice(&b));
+        assert!(l.basis[1].norm.as_ref().unwrap() < &100);
+        assert_eq!(l.exact_gso().d, b.exact_gso().d);
+    }
+}
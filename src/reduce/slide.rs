@@ -0,0 +1,170 @@
+use super::bkz::{shortest_dual_projected, shortest_projected};
+use super::state::Reduction;
+use crate::{Field, Lattice, Ring, Vector};
+
+/**
+
+Implements slide reduction \[GN08\]
+
+The basis is cut into disjoint blocks b_{iβ}, .., b_{(i+1)β-1}. Each primal
+step SVP-reduces every block by enumeration, inserting a shortest vector of
+the projected block, and each dual step DSVP-reduces the shifted blocks
+b_{iβ+1}, .., b_{(i+1)β}, making ‖b*_{(i+1)β}‖ as large as possible through
+a shortest vector of the dual block. Reduction stops once no dual block can
+be improved by a factor δ. Unlike BKZ, slide reduction provably reaches a
+Hermite factor ‖b_0‖ / vol(L)^(1/n) of about γ_β^((n-1)/(2(β-1))) in a
+polynomial number of SVP calls. The output is LLL-reduced.
+
+# Examples
+
+```rust
+use rand::SeedableRng;
+use rand_chacha::ChaCha20Rng;
+use rug::Integer;
+use svp::{Enumerate, Lattice, Slide};
+
+let l = Lattice::random_q_ary(6, 12, &Integer::from(97), &mut ChaCha20Rng::seed_from_u64(1));
+let v = l.shortest();
+
+// Blocks of size β = 6, and a single block solving SVP
+let (mut s6, mut s12) = (l.clone(), l.clone());
+s6.slide(6, 0.99);
+s12.slide(12, 0.99);
+assert_eq!(s6.basis.len(), 12);
+assert_eq!(s12.basis[0].norm, v.norm);
+```
+**/
+pub trait Slide<T> {
+    /// Slide-reduce the basis in place with block size β ≥ 2 and
+    /// parameter δ ∈ (1/4, 1), removing linear dependencies
+    fn slide(&mut self, beta: usize, delta: f64);
+
+    /// Like `slide`, but also return the transformation U as in `LLL::lll_transform`
+    fn slide_transform(&mut self, beta: usize, delta: f64) -> Vec<Vector<T>>;
+}
+
+/// Run slide reduction, optionally tracking the transformation
+fn run<T: Ring>(l: &mut Lattice<T>, beta: usize, delta: f64, track: bool) -> Vec<Vector<T>> {
+    assert!(beta >= 2, "block size must be at least 2");
+    let mut r = Reduction::new(l, track, 1);
+    let n = r.b.len();
+    if n == 0 {
+        return r.finish();
+    }
+    let delta = T::Field::from_f64(delta, r.prec());
+    let n = r.lll(&delta, 0, n);
+    let relations = r.relations();
+
+    loop {
+        // Primal step: SVP-reduce the disjoint blocks until none changes
+        let mut changed = true;
+        while changed {
+            changed = false;
+            for k in (0..n).step_by(beta) {
+                let h = (k + beta).min(n);
+                if h - k < 2 {
+                    continue;
+                }
+                if let Some(x) = shortest_projected(&r, k, h, &delta) {
+                    r.insert(k, &x);
+                    r.lll(&delta, k, n + 1);
+                    r.truncate_relations(relations);
+                    changed = true;
+                }
+            }
+        }
+
+        // Dual step: DSVP-reduce the shifted blocks
+        let mut changed = false;
+        for k in (1..n).step_by(beta) {
+            let h = k + beta;
+            if h > n {
+                break;
+            }
+            if let Some(x) = shortest_dual_projected(&r, k, h, &delta) {
+                r.insert_dual(k, h, &x, &delta);
+                changed = true;
+            }
+        }
+        if !changed {
+            break;
+        }
+        r.lll(&delta, 0, n);
+    }
+    r.finish()
+}
+
+impl<T: Ring> Slide<T> for Lattice<T> {
+    fn slide(&mut self, beta: usize, delta: f64) {
+        run(self, beta, delta, false);
+    }
+
+    fn slide_transform(&mut self, beta: usize, delta: f64) -> Vec<Vector<T>> {
+        run(self, beta, delta, true)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::testing;
+    use crate::*;
+    use rug::Integer;
+
+    #[test]
+    fn test_prim() {
+        let l = testing::dim10();
+        for beta in [2, 3, 5] {
+            let mut r = l.clone();
+            let u = r.slide_transform(beta, 0.99);
+            assert_eq!(r.basis.len(), 10);
+            testing::assert_transform(&l, &r, &u);
+        }
+
+        // A single block is solved exactly
+        let mut r = l.clone();
+        r.slide(10, 0.99);
+        assert_eq!(r.basis[0].norm, Some(5));
+    }
+
+    #[test]
+    fn test_generating_set() {
+        let mut l = testing::generating_set();
+        let g = l.clone();
+        let u = l.slide_transform(2, 0.99);
+        assert_eq!(l.basis.len(), 3);
+        testing::assert_transform(&g, &l, &u);
+    }
+
+    #[test]
+    fn test_mp() {
+        // Nearly parallel vectors with 200-bit entries
+        let big: Integer = Integer::from(1) << 200;
+        let mut l = Lattice {
+            basis: vec![
+                nvec![
+                    big.clone() + 1,
+                    big.clone(),
+                    Integer::from(3),
+                    Integer::new()
+                ],
+                nvec![
+                    big.clone(),
+                    big.clone() - 1,
+                    Integer::from(5),
+                    Integer::new()
+                ],
+                nvec![big.clone(), big.clone(), Integer::from(1), Integer::from(1)],
+                nvec![
+                    Integer::new(),
+                    Integer::from(2),
+                    Integer::from(7),
+                    Integer::from(3)
+                ],
+            ],
+        };
+        let g = l.clone();
+        l.slide(2, 0.99);
+        assert!(l.same_lattice(&g));
+        assert!(l.basis[0].norm.as_ref().unwrap() < &100);
+    }
+}
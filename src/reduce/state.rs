@@ -0,0 +1,287 @@
+use crate::{nvec, Field, Lattice, Ring, Vector};
+
+/// Floating point Gram-Schmidt coefficients of a basis under reduction
+pub(super) struct Coefficients<F> {
+    pub mu: Vec<Vec<F>>, // μ_{i,j} for j < i
+    pub r: Vec<F>,       // ‖b*_i‖²
+    pub prec: u32,
+}
+
+impl<F: Field> Coefficients<F> {
+    /// Room for the coefficients of up to n basis vectors
+    pub fn new(n: usize, prec: u32) -> Self {
+        Self {
+            mu: vec![vec![F::zero(prec); n]; n],
+            r: vec![F::zero(prec); n],
+            prec,
+        }
+    }
+
+    /// Recompute μ_{k,j} and ‖b*_k‖² from exact inner products, assuming
+    /// the coefficients of b_0, .., b_{k-1} are up to date
+    pub fn update<T: Ring<Field = F>>(&mut self, b: &[Vector<T>], k: usize) {
+        let mut rk: Vec<F> = Vec::with_capacity(k);
+        for j in 0..k {
+            let mut x = (&b[k] * &b[j]).to_field(self.prec);
+            for i in 0..j {
+                let mut tmp = self.mu[j][i].clone();
+                tmp *= &rk[i];
+                x -= &tmp;
+            }
+            let mut mu = x.clone();
+            mu /= &self.r[j];
+            self.mu[k][j] = mu;
+            rk.push(x);
+        }
+        let mut x = (&b[k] * &b[k]).to_field(self.prec);
+        for j in 0..k {
+            let mut tmp = self.mu[k][j].clone();
+            tmp *= &rk[j];
+            x -= &tmp;
+        }
+        self.r[k] = x;
+    }
+
+    /// Test the Lovász condition ‖b*_k‖² ≥ (δ - μ_{k,k-1}²) ‖b*_{k-1}‖²
+    pub fn lovasz(&self, k: usize, delta: &F) -> bool {
+        let mut bound = self.mu[k][k - 1].clone();
+        bound *= &self.mu[k][k - 1];
+        let mut rhs = delta.clone();
+        rhs -= &bound;
+        rhs *= &self.r[k - 1];
+        self.r[k] >= rhs
+    }
+}
+
+/// A basis under reduction, along with its floating GSO and optionally the
+/// transformation U with B' = U·B
+pub(super) struct Reduction<'a, T: Ring> {
+    pub b: &'a mut Vec<Vector<T>>,
+    pub c: Coefficients<T::Field>,
+    u: Option<Vec<Vector<T>>>,
+    relations: Vec<Vector<T>>, // rows of U of the removed zero vectors
+}
+
+/// Test whether every coordinate of v vanishes
+pub(super) fn is_zero<T: Ring>(v: &Vector<T>) -> bool {
+    v.vec.iter().all(|x| x.is_zero())
+}
+
+/// The n×n identity matrix
+pub(super) fn identity<T: Ring>(n: usize) -> Vec<Vector<T>> {
+    (0..n)
+        .map(|i| {
+            let mut e = nvec![T::zero(); n];
+            e.vec[i] = T::one();
+            e
+        })
+        .collect()
+}
+
+impl<'a, T: Ring> Reduction<'a, T> {
+    /// Start reducing the basis of `l`, with room for `extra` inserted vectors
+    pub fn new(l: &'a mut Lattice<T>, track: bool, extra: usize) -> Self {
+        let prec = l.estimate_precision().max(T::PRECISION);
+        let n = l.basis.len();
+        let mut s = Self {
+            c: Coefficients::new(n + extra, prec),
+            u: match track {
+                true => Some(identity(n)),
+                false => None,
+            },
+            b: &mut l.basis,
+            relations: vec![],
+        };
+
+        // Zero vectors are relations of the generating set
+        let mut k = 0;
+        while k < s.b.len() {
+            match is_zero(&s.b[k]) {
+                true => s.remove(k),
+                false => k += 1,
+            }
+        }
+        s
+    }
+
+    pub fn prec(&self) -> u32 {
+        self.c.prec
+    }
+
+    /// b_k -= q·b_j, with μ_{k,i} updated for i ≤ j
+    fn sub_mul(&mut self, k: usize, j: usize, q: &T::Field) {
+        let qi = T::from_field(q).expect("machine integer overflow");
+        let tmp = &self.b[j] * qi.clone();
+        self.b[k] -= &tmp;
+        if let Some(u) = self.u.as_mut() {
+            let tmp = &u[j] * qi;
+            u[k] -= &tmp;
+        }
+        for i in 0..j {
+            let mut tmp = self.c.mu[j][i].clone();
+            tmp *= q;
+            self.c.mu[k][i] -= &tmp;
+        }
+        self.c.mu[k][j] -= q;
+    }
+
+    /// Size reduce b_k against b_0, .., b_{k-1} until |μ_{k,j}| ≤ 0.51,
+    /// assuming the coefficients of b_0, .., b_{k-1} are up to date
+    pub fn size_reduce(&mut self, k: usize) {
+        let prec = self.prec();
+        let eta = T::Field::from_f64(0.51, prec);
+        let mut neg_eta = T::Field::zero(prec);
+        neg_eta -= &eta;
+        loop {
+            self.c.update(self.b, k);
+            if self.c.mu[k][..k]
+                .iter()
+                .all(|mu| mu <= &eta && mu >= &neg_eta)
+            {
+                return;
+            }
+            for j in (0..k).rev() {
+                let q = self.c.mu[k][j].round();
+                if q != T::Field::zero(prec) {
+                    self.sub_mul(k, j, &q);
+                }
+            }
+        }
+    }
+
+    /// Swap b_k and b_j
+    pub fn swap(&mut self, k: usize, j: usize) {
+        self.b.swap(k, j);
+        if let Some(u) = self.u.as_mut() {
+            u.swap(k, j);
+        }
+    }
+
+    /// Move b_k to position i < k, shifting b_i, .., b_{k-1} up by one
+    pub fn rotate(&mut self, i: usize, k: usize) {
+        self.b[i..=k].rotate_right(1);
+        if let Some(u) = self.u.as_mut() {
+            u[i..=k].rotate_right(1);
+        }
+    }
+
+    /// Remove the zero vector b_k, keeping its row of U as a relation
+    pub fn remove(&mut self, k: usize) {
+        self.b.remove(k);
+        if let Some(u) = self.u.as_mut() {
+            self.relations.push(u.remove(k));
+        }
+    }
+
+    /// Insert the combination Σ x_i b_{k+i} at position k
+    pub fn insert(&mut self, k: usize, x: &[T]) {
+        let m = self.b[0].vec.len();
+        let mut v = nvec![T::zero(); m];
+        for (i, xi) in x.iter().enumerate() {
+            v += &(&self.b[k + i] * xi.clone());
+        }
+        self.b.insert(k, v);
+        if let Some(u) = self.u.as_mut() {
+            let mut w = nvec![T::zero(); u[0].vec.len()];
+            for (i, xi) in x.iter().enumerate() {
+                w += &(&u[k + i] * xi.clone());
+            }
+            u.insert(k, w);
+        }
+    }
+
+    /// b_k -= q·b_j, leaving the coefficients from b_min(j,k) on out of date
+    fn sub_multiple(&mut self, k: usize, j: usize, q: &T) {
+        let tmp = &self.b[j] * q.clone();
+        self.b[k] -= &tmp;
+        if let Some(u) = self.u.as_mut() {
+            let tmp = &u[j] * q.clone();
+            u[k] -= &tmp;
+        }
+    }
+
+    /// Make the dual vector w with ⟨w, b_{k+i}⟩ = x_i the last vector of the
+    /// dual basis of b_k, .., b_{h-1}, then LLL-reduce b_k, .., b_{h-2} and
+    /// size reduce the rest, which leaves ‖b*_{h-1}‖ = 1 / ‖w‖
+    ///
+    /// x is primitive for a shortest dual vector, so Euclid's algorithm on x,
+    /// mirrored by row operations on the block, reduces it to ±e_{h-1}.
+    pub fn insert_dual(&mut self, k: usize, h: usize, x: &[T], delta: &T::Field) {
+        let mut x = x.to_vec();
+        loop {
+            let support: Vec<usize> = (0..x.len()).filter(|i| !x[*i].is_zero()).collect();
+            let i = *support
+                .iter()
+                .min_by(|a, b| x[**a].abs().partial_cmp(&x[**b].abs()).unwrap())
+                .unwrap();
+            if support.len() == 1 {
+                self.swap(k + i, h - 1);
+                break;
+            }
+            for j in support {
+                if j == i {
+                    continue;
+                }
+                let q = x[j].round_div(&x[i]);
+                let mut tmp = x[i].clone();
+                tmp *= &q;
+                x[j] -= &tmp;
+                self.sub_multiple(k + j, k + i, &q);
+            }
+        }
+
+        let n = self.b.len();
+        self.lll(delta, k, h - 1);
+        for i in h - 1..n {
+            self.size_reduce(i);
+        }
+    }
+
+    /// The number of relations found so far
+    pub fn relations(&self) -> usize {
+        self.relations.len()
+    }
+
+    /// Forget the relations introduced by insertions after the first `len`
+    pub fn truncate_relations(&mut self, len: usize) {
+        self.relations.truncate(len);
+    }
+
+    /// LLL-reduce b_0, .., b_{end-1}, assuming b_0, .., b_{k-1} already are,
+    /// and return the new end after removing linear dependencies
+    pub fn lll(&mut self, delta: &T::Field, mut k: usize, mut end: usize) -> usize {
+        if k == 0 {
+            self.c.update(self.b, 0);
+            k = 1;
+        }
+        while k < end {
+            self.size_reduce(k);
+            if is_zero(&self.b[k]) {
+                self.remove(k);
+                end -= 1;
+                continue;
+            }
+            if self.c.lovasz(k, delta) {
+                k += 1;
+                continue;
+            }
+            self.swap(k, k - 1);
+            if k == 1 {
+                self.c.update(self.b, 0);
+            }
+            k = (k - 1).max(1);
+        }
+        end
+    }
+
+    /// Store the squared norms of the reduced basis vectors, and return U
+    /// followed by the relations
+    pub fn finish(self) -> Vec<Vector<T>> {
+        for v in self.b.iter_mut() {
+            v.norm = Some(&*v * &*v);
+        }
+        let mut u = self.u.unwrap_or_default();
+        u.extend(self.relations);
+        u
+    }
+}
@@ -0,0 +1,130 @@
+use rand::Rng;
+
+/// Number of fractional center positions with a precomputed table
+pub(crate) const CDT_RESOLUTION: i64 = 64;
+
+/**
+
+Table-based sampler for the discrete gaussian over the integers
+
+Centers are rounded to the nearest multiple of 1/`CDT_RESOLUTION`, and one
+cumulative distribution table is kept per fractional position and coordinate.
+Every call draws exactly one uniform 63-bit deviate and scans every entry of
+every table of that coordinate, so neither the control flow nor the number of
+operations depends on the center or on the sampled value.
+**/
+
+#[derive(Debug, Clone)]
+pub(crate) struct Cdt {
+    tail: Vec<i64>,        // lower tail bound per coordinate
+    tables: Vec<Vec<u64>>, // CDT_RESOLUTION tables of 2 * tail + 2 entries per coordinate
+}
+
+impl Cdt {
+    /// Build the tables for parameters s2 (see `SampleZ`) and tail cut t
+    pub(crate) fn new(s2: &[f64], t: f64) -> Self {
+        let scale = (1u64 << 63) as f64;
+        let mut tail = vec![];
+        let mut tables = vec![];
+
+        for s2 in s2 {
+            let w = (s2.sqrt() * t).ceil() as i64 + 1;
+            let len = (2 * w + 2) as usize;
+            let mut table = Vec::with_capacity(len * CDT_RESOLUTION as usize);
+
+            for q in 0..CDT_RESOLUTION {
+                let c = q as f64 / CDT_RESOLUTION as f64;
+                let rho: Vec<f64> = (-w..=w + 1)
+                    .map(|x| (-std::f64::consts::PI * (x as f64 - c).powi(2) / s2).exp())
+                    .collect();
+                let total: f64 = rho.iter().sum();
+                let mut acc = 0f64;
+                for (k, r) in rho.iter().enumerate() {
+                    acc += r;
+                    table.push(match k + 1 == len {
+                        true => 1u64 << 63,
+                        false => ((acc / total) * scale).min(scale) as u64,
+                    });
+                }
+            }
+
+            tail.push(w);
+            tables.push(table);
+        }
+
+        Self { tail, tables }
+    }
+
+    /// Sample an offset x such that `k + x` is distributed around `k + q / CDT_RESOLUTION`
+    pub(crate) fn sample<R: Rng + ?Sized>(&self, i: usize, q: i64, rng: &mut R) -> i64 {
+        self.scan(i, q, rng.gen::<u64>() >> 1).0
+    }
+
+    /// Look up the 63-bit deviate u in the tables of coordinate i, returning
+    /// the offset along with the number of comparisons made
+    fn scan(&self, i: usize, q: i64, u: u64) -> (i64, usize) {
+        let len = (2 * self.tail[i] + 2) as usize;
+        let mut count = 0u64;
+        let mut steps = 0;
+
+        for (j, row) in self.tables[i].chunks_exact(len).enumerate() {
+            let mask = 0u64.wrapping_sub((j as i64 == q) as u64);
+            for th in row {
+                count += mask & (u >= *th) as u64;
+                steps += 1;
+            }
+        }
+
+        (count as i64 - self.tail[i], steps)
+    }
+}
+
+/// Split a center into its integer part and rounded fractional position
+#[inline]
+pub(crate) fn split_center(c: f64) -> (i64, i64) {
+    let scaled = (c * CDT_RESOLUTION as f64).round() as i64;
+    (
+        scaled.div_euclid(CDT_RESOLUTION),
+        scaled.rem_euclid(CDT_RESOLUTION),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_distribution() {
+        let cdt = Cdt::new(&[4.0, 25.0], 3.0);
+        let mut rng = rand::thread_rng();
+        for (i, c) in [(0, 0.0), (0, 3.25), (1, -7.5)] {
+            let n = 20000;
+            let (k, q) = split_center(c);
+            let sum: i64 = (0..n).map(|_| k + cdt.sample(i, q, &mut rng)).sum();
+            let mean = sum as f64 / n as f64;
+            assert!((mean - c).abs() < 0.1, "mean {mean} too far from {c}");
+        }
+    }
+
+    #[test]
+    fn test_constant_time() {
+        let cdt = Cdt::new(&[4.0, 25.0], 3.0);
+        for i in 0..2 {
+            let len = (2 * cdt.tail[i] + 2) as usize;
+            for q in 0..CDT_RESOLUTION {
+                // Deviates on both sides of every threshold reach every likely offset,
+                // with the same number of comparisons
+                let row = &cdt.tables[i][q as usize * len..(q as usize + 1) * len];
+                let mut offsets = vec![];
+                for u in row.iter().flat_map(|th| [th.saturating_sub(1), *th]) {
+                    let (x, steps) = cdt.scan(i, q, u.min((1 << 63) - 1));
+                    assert_eq!(steps, cdt.tables[i].len());
+                    offsets.push(x);
+                }
+                offsets.sort();
+                offsets.dedup();
+                assert!(offsets.len() > cdt.tail[i] as usize);
+            }
+        }
+    }
+}
@@ -1,6 +1,6 @@
-use crate::{nvec, Lattice, Vector};
+use super::cdt::{split_center, Cdt};
+use crate::{nvec, Coordinates, Field, GramLattice, Lattice, Overflow, Ring, Vector};
 use rand::Rng;
-use rug::{Float, Integer};
 
 /**
 
@@ -61,6 +61,56 @@ for _ in 0..10 {
     let s : Vector<Integer> = k.sample(&l);
 }
 ```
+
+Sampling with a running time independent of the sampled values
+
+```rust
+use svp::{nvec, KleinSampler, Lattice, Sample, SampleMode, Vector, GSO};
+
+let l = Lattice {
+    basis: vec![nvec![1, 1, 0], nvec![1, 2, 0], nvec![0, 1, 2]],
+};
+let gs = l.gso();
+let k = KleinSampler::init(&gs, (gs.len() as f64).ln()).with_mode(SampleMode::ConstantTime);
+assert_eq!(k.mode(), SampleMode::ConstantTime);
+
+let s: Vector<i64> = k.sample(&l);
+```
+
+Drawing many samples at once amortizes allocations
+
+```rust
+use svp::{nvec, KleinSampler, Lattice, Sample, Vector, GSO};
+
+let l = Lattice {
+    basis: vec![nvec![1, 1, 0], nvec![1, 2, 0], nvec![0, 1, 2]],
+};
+let gs = l.gso();
+let k = KleinSampler::init(&gs, (gs.len() as f64).ln());
+let mut rng = rand::thread_rng();
+
+// Lattice points only
+let samples: Vec<Vector<i64>> = k.sample_batch(&l, 1000, &mut rng);
+
+// Lattice points along with their coefficient vectors
+for (v, z) in k.sample_batch_coef(&l, 1000, &mut rng) {
+    assert_eq!(v.vec, (&z * &l).vec);
+}
+```
+
+Lattices given by a Gram matrix are sampled in coefficient space
+
+```rust
+use svp::{nvec, GramLattice, KleinSampler, SampleGram, Vector, GSO};
+
+let g = GramLattice::new(vec![nvec![2i64, -1], nvec![-1, 2]]);
+let gs = g.gso();
+let k = KleinSampler::init(&gs, (gs.len() as f64).ln());
+
+for z in k.sample_gram_batch(&g, 100, &mut rand::thread_rng()) {
+    assert_eq!(z.norm, Some(g.norm(&z)));
+}
+```
 **/
 
 #[derive(Debug)]
@@ -68,24 +118,124 @@ pub struct KleinSampler<T> {
     gs: Vec<Vector<T>>, // Gram-Schmidt matrix
     t: T,               // rejection sampling parameter
     s2: Vec<T>,
+    cdt: Option<Cdt>, // tables for constant-time sampling
+}
+
+/// Strategy used to sample from the discrete gaussian over the integers
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SampleMode {
+    /// Rejection sampling, fast but with value-dependent running time
+    Rejection,
+    /// Table-based CDT sampling with one uniform draw per coordinate
+    ///
+    /// Centers are rounded to the nearest 1/64, and the tables grow with
+    /// the gaussian width, so this mode is meant for small prototypes.
+    ///
+    /// Only the machine integer backends, with `f64` Gram-Schmidt vectors,
+    /// are constant-time. With `Integer` lattices the centers are updated
+    /// in `Float` arithmetic, whose running time depends on the values.
+    ConstantTime,
 }
 
 /// Rejection sample from the discrete gaussian
 trait SampleZ<T> {
-    fn sample_z(&self, c: &T, s: &T) -> T;
+    fn sample_z<R: Rng + ?Sized>(&self, c: &T, s: &T, rng: &mut R) -> T;
+}
+
+/// Sample an integer coefficient vector into `z`, using `buf` as scratch space,
+/// failing if a coefficient does not fit into `T`
+trait SampleCoef<T, U> {
+    fn sample_coef<R: Rng + ?Sized>(
+        &self,
+        buf: &mut Vector<U>,
+        z: &mut Vector<T>,
+        rng: &mut R,
+    ) -> Result<(), Overflow>;
 }
 
 /// The SampleD subroutine as described in \[GPV08\]
 pub trait Sample<T> {
     fn sample(&self, l: &Lattice<T>) -> Vector<T>;
-}
 
-impl<T> KleinSampler<T> {
-    /// Initialize the `KleinSampler`
-    pub fn init(gs: &Vec<Vector<T>>, t: T) -> Self
+    /// Sample `n` lattice points, reusing the same buffers for every draw
+    ///
+    /// The default calls `sample` n times, which draws from its own generator.
+    fn sample_batch<R: Rng + ?Sized>(
+        &self,
+        l: &Lattice<T>,
+        n: usize,
+        _rng: &mut R,
+    ) -> Vec<Vector<T>> {
+        (0..n).map(|_| self.sample(l)).collect()
+    }
+
+    /// Like `sample_batch`, but fails instead of overflowing machine integers
+    ///
+    /// The default only recomputes the norms of the points from `sample`.
+    fn try_sample_batch<R: Rng + ?Sized>(
+        &self,
+        l: &Lattice<T>,
+        n: usize,
+        _rng: &mut R,
+    ) -> Result<Vec<Vector<T>>, Overflow>
     where
-        T: std::ops::Mul<T, Output = T> + std::ops::Div<T, Output = T> + Clone + PartialOrd,
+        T: Ring,
     {
+        (0..n)
+            .map(|_| {
+                let mut v = self.sample(l);
+                v.norm = Some(T::checked_dot(&v.vec, &v.vec)?);
+                Ok(v)
+            })
+            .collect()
+    }
+
+    /// Sample `n` lattice points along with their coefficient vectors w.r.t. the basis
+    ///
+    /// The default recovers the coefficients of the points from `sample`.
+    fn sample_batch_coef<R: Rng + ?Sized>(
+        &self,
+        l: &Lattice<T>,
+        n: usize,
+        _rng: &mut R,
+    ) -> Vec<(Vector<T>, Vector<T>)>
+    where
+        Lattice<T>: Coordinates<T>,
+    {
+        (0..n)
+            .map(|_| {
+                let v = self.sample(l);
+                let z = l.coordinates(&v).expect("sample is not a lattice point");
+                (v, z)
+            })
+            .collect()
+    }
+}
+
+/// The SampleD subroutine in the coefficient space of a `GramLattice`
+///
+/// The sampler must be initialized with the GSO of the `GramLattice`.
+pub trait SampleGram<T> {
+    /// Sample `n` coefficient vectors, with norms taken with respect to G
+    fn sample_gram_batch<R: Rng + ?Sized>(
+        &self,
+        g: &GramLattice<T>,
+        n: usize,
+        rng: &mut R,
+    ) -> Vec<Vector<T>>;
+
+    /// Like `sample_gram_batch`, but fails instead of overflowing machine integers
+    fn try_sample_gram_batch<R: Rng + ?Sized>(
+        &self,
+        g: &GramLattice<T>,
+        n: usize,
+        rng: &mut R,
+    ) -> Result<Vec<Vector<T>>, Overflow>;
+}
+
+impl<T: Field> KleinSampler<T> {
+    /// Initialize the `KleinSampler`
+    pub fn init(gs: &Vec<Vector<T>>, t: T) -> Self {
         let mut max_norm: T = gs[0].norm.clone().unwrap();
         for g in gs {
             if g.norm.clone().unwrap() > max_norm {
@@ -93,100 +243,241 @@ impl<T> KleinSampler<T> {
             }
         }
 
-        let s = max_norm * t.clone();
+        let mut s = max_norm;
+        s *= &t;
         let s2 = gs
             .iter()
-            .map(|i| s.clone() / i.norm.clone().unwrap())
+            .map(|i| {
+                let mut x = s.clone();
+                x /= i.norm.as_ref().unwrap();
+                x
+            })
             .collect();
 
         Self {
             gs: gs.to_vec(),
             t,
             s2,
+            cdt: None,
+        }
+    }
+
+    /// Select the discrete gaussian sampling strategy
+    pub fn with_mode(mut self, mode: SampleMode) -> Self {
+        self.cdt = match mode {
+            SampleMode::Rejection => None,
+            SampleMode::ConstantTime => {
+                let s2: Vec<f64> = self.s2.iter().map(|i| i.to_f64()).collect();
+                Some(Cdt::new(&s2, self.t.to_f64()))
+            }
+        };
+        self
+    }
+
+    /// The rejection sampling parameter
+    pub fn t(&self) -> &T {
+        &self.t
+    }
+
+    /// The discrete gaussian sampling strategy in use
+    pub fn mode(&self) -> SampleMode {
+        match self.cdt {
+            Some(_) => SampleMode::ConstantTime,
+            None => SampleMode::Rejection,
         }
     }
+
+    /// Precision of the Gram-Schmidt matrix
+    fn prec(&self) -> u32 {
+        self.gs[0].vec[0].prec()
+    }
 }
 
-impl SampleZ<f64> for KleinSampler<f64> {
+impl<T: Field> SampleZ<T> for KleinSampler<T> {
     /// Rejection sample from the discrete gaussian
-    fn sample_z(&self, c: &f64, s2: &f64) -> f64 {
-        let s = s2.sqrt();
-        let min = (c - s * self.t).floor();
-        let max = (c + s * self.t).ceil();
+    fn sample_z<R: Rng + ?Sized>(&self, c: &T, s2: &T, rng: &mut R) -> T {
+        let prec = c.prec();
+        let mut w = s2.sqrt();
+        w *= &self.t;
+        let mut min = c.clone();
+        min -= &w;
+        let min = min.floor();
+        let mut max = c.clone();
+        max += &w;
+        let mut delta = max.ceil();
+        delta -= &min;
+        // The acceptance probability exp(-π·(x - c)²/s2) is computed in T
+        let mut scale = T::pi(prec);
+        scale /= s2;
 
-        let mut rng = rand::thread_rng();
         loop {
             let deviate = rng.gen_range(0.0..1.0);
-            let x = min + ((max - min) * deviate).round();
-            let r = (-std::f64::consts::PI * (x - c) * (x - c) / s2).exp();
-            if rng.gen_range(0.0..1.0) <= r {
+            let mut x = delta.clone();
+            x *= &T::from_f64(deviate, prec);
+            let mut x = x.round();
+            x += &min;
+            let mut u = x.clone();
+            u -= c;
+            let tmp = u.clone();
+            u *= &tmp;
+            u *= &scale;
+            let mut r = T::zero(prec);
+            r -= &u;
+            if T::from_f64(rng.gen_range(0.0..1.0), prec) <= r.exp() {
                 return x;
             }
         }
     }
 }
 
-impl Sample<i64> for KleinSampler<f64> {
+impl<T: Ring> SampleCoef<T, T::Field> for KleinSampler<T::Field> {
     /// Sample a coefficient vector
-    fn sample(&self, l: &Lattice<i64>) -> Vector<i64> {
-        let mut coef = nvec![0f64; self.gs.len()];
-        for i in (0..coef.vec.len()).rev() {
-            coef.vec[i] = self.sample_z(&coef.vec[i], &self.s2[i]);
+    fn sample_coef<R: Rng + ?Sized>(
+        &self,
+        buf: &mut Vector<T::Field>,
+        z: &mut Vector<T>,
+        rng: &mut R,
+    ) -> Result<(), Overflow> {
+        let prec = self.prec();
+        for c in buf.vec.iter_mut() {
+            *c = T::Field::zero(prec);
+        }
+        for i in (0..buf.vec.len()).rev() {
+            buf.vec[i] = match &self.cdt {
+                Some(cdt) => {
+                    let k = buf.vec[i].floor();
+                    let mut frac = buf.vec[i].clone();
+                    frac -= &k;
+                    let (carry, q) = split_center(frac.to_f64());
+                    let mut x = T::Field::from_f64((carry + cdt.sample(i, q, rng)) as f64, prec);
+                    x += &k;
+                    x
+                }
+                None => self.sample_z(&buf.vec[i], &self.s2[i], rng),
+            };
+            let zi = buf.vec[i].clone();
             for j in 0..i {
-                coef.vec[j] -= coef.vec[i] * self.gs[i].vec[j];
+                let mut tmp = self.gs[i].vec[j].clone();
+                tmp *= &zi;
+                buf.vec[j] -= &tmp;
             }
+            z.vec[i] = T::from_field(&zi)?;
         }
-        l * &coef
+        Ok(())
     }
 }
 
-impl SampleZ<Float> for KleinSampler<Float> {
-    /// Rejection sample from the discrete gaussian with arbitrary precision
-    fn sample_z(&self, c: &Float, s2: &Float) -> Float {
-        let prec = c.prec();
-        let s = s2.clone().sqrt();
-        let min = Float::with_val(prec, c - &s * &self.t).floor();
-        let max = Float::with_val(prec, c + &s * &self.t).ceil();
-        let delta: Float = max - min.clone();
-        let mut rng = rand::thread_rng();
+impl<T: Ring> Sample<T> for KleinSampler<T::Field> {
+    /// Sample a lattice point
+    fn sample(&self, l: &Lattice<T>) -> Vector<T> {
+        let mut coef = nvec![T::Field::zero(self.prec()); self.gs.len()];
+        let mut z = nvec![T::zero(); self.gs.len()];
+        self.sample_coef(&mut coef, &mut z, &mut rand::thread_rng())
+            .expect("machine integer overflow");
+        &z * l
+    }
 
-        loop {
-            let deviate = rng.gen_range(0.0..1.0);
-            let x: Float = Float::with_val(prec, &delta * deviate).round() + &min;
-            let u = Float::with_val(prec, &x - c).square();
-            let r = (-std::f64::consts::PI * u / s2).exp();
-            if rng.gen_range(0.0..1.0) <= r {
-                return x;
-            }
-        }
+    fn sample_batch<R: Rng + ?Sized>(
+        &self,
+        l: &Lattice<T>,
+        n: usize,
+        rng: &mut R,
+    ) -> Vec<Vector<T>> {
+        let mut coef = nvec![T::Field::zero(self.prec()); self.gs.len()];
+        let mut z = nvec![T::zero(); self.gs.len()];
+        (0..n)
+            .map(|_| {
+                self.sample_coef(&mut coef, &mut z, rng)
+                    .expect("machine integer overflow");
+                &z * l
+            })
+            .collect()
+    }
+
+    fn try_sample_batch<R: Rng + ?Sized>(
+        &self,
+        l: &Lattice<T>,
+        n: usize,
+        rng: &mut R,
+    ) -> Result<Vec<Vector<T>>, Overflow> {
+        let mut coef = nvec![T::Field::zero(self.prec()); self.gs.len()];
+        let mut z = nvec![T::zero(); self.gs.len()];
+        (0..n)
+            .map(|_| {
+                self.sample_coef(&mut coef, &mut z, rng)?;
+                l.checked_combine(&z)
+            })
+            .collect()
+    }
+
+    fn sample_batch_coef<R: Rng + ?Sized>(
+        &self,
+        l: &Lattice<T>,
+        n: usize,
+        rng: &mut R,
+    ) -> Vec<(Vector<T>, Vector<T>)> {
+        let mut coef = nvec![T::Field::zero(self.prec()); self.gs.len()];
+        let mut z = nvec![T::zero(); self.gs.len()];
+        (0..n)
+            .map(|_| {
+                self.sample_coef(&mut coef, &mut z, rng)
+                    .expect("machine integer overflow");
+                (&z * l, z.clone())
+            })
+            .collect()
     }
 }
 
-impl Sample<Integer> for KleinSampler<Float> {
-    /// Sample a coefficient vector with arbitrary precision
-    fn sample(&self, l: &Lattice<Integer>) -> Vector<Integer> {
-        let prec = self.gs[0].vec[0].prec();
-        let mut coef = nvec![Float::new(prec); self.gs.len()];
-        for i in (0..coef.vec.len()).rev() {
-            coef.vec[i] = Float::with_val(prec, self.sample_z(&coef.vec[i], &self.s2[i]));
-            for j in 0..i {
-                let tmp = Float::with_val(prec, &self.gs[i].vec[j] * &coef.vec[i]);
-                coef.vec[j] -= tmp;
-            }
-        }
-        l * &coef
+impl<T: Ring> SampleGram<T> for KleinSampler<T::Field> {
+    fn sample_gram_batch<R: Rng + ?Sized>(
+        &self,
+        g: &GramLattice<T>,
+        n: usize,
+        rng: &mut R,
+    ) -> Vec<Vector<T>> {
+        let mut coef = nvec![T::Field::zero(self.prec()); self.gs.len()];
+        let mut z = nvec![T::zero(); self.gs.len()];
+        (0..n)
+            .map(|_| {
+                self.sample_coef(&mut coef, &mut z, rng)
+                    .expect("machine integer overflow");
+                z.norm = Some(g.norm(&z));
+                z.clone()
+            })
+            .collect()
+    }
+
+    fn try_sample_gram_batch<R: Rng + ?Sized>(
+        &self,
+        g: &GramLattice<T>,
+        n: usize,
+        rng: &mut R,
+    ) -> Result<Vec<Vector<T>>, Overflow> {
+        let mut coef = nvec![T::Field::zero(self.prec()); self.gs.len()];
+        let mut z = nvec![T::zero(); self.gs.len()];
+        (0..n)
+            .map(|_| {
+                self.sample_coef(&mut coef, &mut z, rng)?;
+                z.norm = Some(g.checked_inner(&z, &z)?);
+                Ok(z.clone())
+            })
+            .collect()
     }
 }
 
 #[cfg(test)]
 mod tests {
     use crate::*;
+    use rand::rngs::mock::StepRng;
+    use rand::{Rng, RngCore, SeedableRng};
+    use rand_chacha::ChaCha20Rng;
     use rug::{Float, Integer};
+    use std::time::Instant;
 
     #[test]
     fn test_prim() {
         let l = Lattice {
-            basis: vec![nvec![1, 1, 0], nvec![1, 2, 0], nvec![0, 1, 2]],
+            basis: vec![nvec![1i64, 1, 0], nvec![1, 2, 0], nvec![0, 1, 2]],
         };
         let gs = l.gso();
         let t = (gs.len() as f64).ln();
@@ -196,6 +487,187 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_batch() {
+        let l = Lattice {
+            basis: vec![nvec![1i64, 1, 0], nvec![1, 2, 0], nvec![0, 1, 2]],
+        };
+        let gs = l.gso();
+        let t = (gs.len() as f64).ln();
+        let k = KleinSampler::init(&gs, t);
+        let mut rng = rand::thread_rng();
+        assert_eq!(k.sample_batch(&l, 100, &mut rng).len(), 100);
+        for (v, z) in k.sample_batch_coef(&l, 100, &mut rng) {
+            assert_eq!(v.vec, (&z * &l).vec);
+            assert_eq!(v.norm, Some(&v * &v));
+        }
+    }
+
+    /// Always returns b_0, relying on the default batch methods
+    struct First;
+
+    impl Sample<i64> for First {
+        fn sample(&self, l: &Lattice<i64>) -> Vector<i64> {
+            let mut v = l.basis[0].clone();
+            v.norm = Some(&v * &v);
+            v
+        }
+    }
+
+    #[test]
+    fn test_default_batch() {
+        let l = Lattice {
+            basis: vec![nvec![1i64, 1, 0], nvec![1, 2, 0], nvec![0, 1, 2]],
+        };
+        let mut rng = rand::thread_rng();
+        assert_eq!(First.sample_batch(&l, 3, &mut rng).len(), 3);
+        assert_eq!(
+            First.try_sample_batch(&l, 3, &mut rng).unwrap()[0].norm,
+            Some(2)
+        );
+        for (v, z) in First.sample_batch_coef(&l, 3, &mut rng) {
+            assert_eq!((v.vec, z.vec), (vec![1, 1, 0], vec![1, 0, 0]));
+        }
+    }
+
+    #[test]
+    fn test_gram() {
+        let l = Lattice {
+            basis: vec![nvec![1i64, 1, 0], nvec![1, 2, 0], nvec![0, 1, 2]],
+        };
+        let g = l.gram();
+        let gs = g.gso();
+        let k = KleinSampler::init(&gs, (gs.len() as f64).ln());
+        let mut rng = rand::thread_rng();
+        for z in k.sample_gram_batch(&g, 100, &mut rng) {
+            assert_eq!(z.norm, (&z * &l).norm);
+        }
+        assert_eq!(k.try_sample_gram_batch(&g, 10, &mut rng).unwrap().len(), 10);
+    }
+
+    #[test]
+    fn test_constant_time() {
+        let l = Lattice {
+            basis: vec![nvec![1i64, 1, 0], nvec![1, 2, 0], nvec![0, 1, 2]],
+        };
+        let gs = l.gso();
+        let t = (gs.len() as f64).ln();
+        let k = KleinSampler::init(&gs, t).with_mode(SampleMode::ConstantTime);
+        let mut rng = rand::thread_rng();
+        for (v, z) in k.sample_batch_coef(&l, 100, &mut rng) {
+            assert_eq!(v.vec, (&z * &l).vec);
+        }
+    }
+
+    /// Counts the bytes drawn from the wrapped generator
+    struct Counting<R> {
+        rng: R,
+        bytes: usize,
+    }
+
+    impl<R: RngCore> RngCore for Counting<R> {
+        fn next_u32(&mut self) -> u32 {
+            self.bytes += 4;
+            self.rng.next_u32()
+        }
+
+        fn next_u64(&mut self) -> u64 {
+            self.bytes += 8;
+            self.rng.next_u64()
+        }
+
+        fn fill_bytes(&mut self, dest: &mut [u8]) {
+            self.bytes += dest.len();
+            self.rng.fill_bytes(dest)
+        }
+
+        fn try_fill_bytes(&mut self, dest: &mut [u8]) -> Result<(), rand::Error> {
+            self.bytes += dest.len();
+            self.rng.try_fill_bytes(dest)
+        }
+    }
+
+    /// Every coordinate consumes exactly one 64-bit draw whatever the random
+    /// inputs, all zeros or uniform
+    #[test]
+    fn test_constant_time_draws() {
+        let l: Lattice<i64> = Lattice {
+            basis: vec![nvec![1, 1, 0], nvec![1, 2, 0], nvec![0, 1, 2]],
+        };
+        let gs = l.gso();
+        let t = (gs.len() as f64).ln();
+        let k = KleinSampler::init(&gs, t).with_mode(SampleMode::ConstantTime);
+        let mut rng = ChaCha20Rng::seed_from_u64(1);
+
+        for i in 0..200 {
+            let inner = match i % 2 {
+                0 => StepRng::new(0, 0),
+                _ => StepRng::new(rng.gen(), rng.gen()),
+            };
+            let mut u = Counting {
+                rng: inner,
+                bytes: 0,
+            };
+            k.sample_batch(&l, 8, &mut u);
+            assert_eq!(u.bytes, 8 * 8 * l.basis.len());
+        }
+    }
+
+    /// Welch's t-statistic between two timing classes
+    fn welch_t(a: &[f64], b: &[f64]) -> f64 {
+        let stats = |x: &[f64]| {
+            let m = x.iter().sum::<f64>() / x.len() as f64;
+            let v = x.iter().map(|i| (i - m).powi(2)).sum::<f64>() / (x.len() - 1) as f64;
+            (m, v)
+        };
+        let (ma, va) = stats(a);
+        let (mb, vb) = stats(b);
+        (ma - mb) / (va / a.len() as f64 + vb / b.len() as f64).sqrt()
+    }
+
+    /// dudect-style fixed-vs-random test of constant-time lattice sampling
+    ///
+    /// Wall-clock timings are too noisy for the regular test suite, run it
+    /// on an idle machine with
+    /// `cargo test --release test_constant_time_leakage -- --ignored`.
+    #[test]
+    #[ignore]
+    fn test_constant_time_leakage() {
+        let l: Lattice<i64> = Lattice {
+            basis: vec![nvec![1, 1, 0], nvec![1, 2, 0], nvec![0, 1, 2]],
+        };
+        let gs = l.gso();
+        let t = (gs.len() as f64).ln();
+        let k = KleinSampler::init(&gs, t).with_mode(SampleMode::ConstantTime);
+        let mut rng = rand::thread_rng();
+        let (mut fixed, mut random) = (vec![], vec![]);
+
+        for _ in 0..20000 {
+            let class = rng.gen::<bool>();
+            let mut u = match class {
+                true => StepRng::new(0, 0),
+                false => StepRng::new(rng.gen(), rng.gen()),
+            };
+            let start = Instant::now();
+            std::hint::black_box(k.sample_batch(&l, 8, &mut u));
+            let elapsed = start.elapsed().as_nanos() as f64;
+            match class {
+                true => fixed.push(elapsed),
+                false => random.push(elapsed),
+            }
+        }
+
+        // Discard outliers caused by preemption, as dudect does
+        let mut all: Vec<f64> = fixed.iter().chain(random.iter()).copied().collect();
+        all.sort_by(|a, b| a.partial_cmp(b).unwrap());
+        let cutoff = all[all.len() * 9 / 10];
+        fixed.retain(|x| *x <= cutoff);
+        random.retain(|x| *x <= cutoff);
+
+        let t = welch_t(&fixed, &random);
+        assert!(t.abs() < 4.5, "timing leak detected: t = {t}");
+    }
+
     #[test]
     fn test_mp() {
         let l = Lattice {
@@ -212,4 +684,41 @@ mod tests {
             assert_eq!(k.sample(&l).vec.len(), l.basis[0].vec.len());
         }
     }
+
+    #[test]
+    fn test_batch_mp() {
+        let l = Lattice {
+            basis: vec![
+                nvec![Integer::from(1), Integer::from(-1), Integer::from(1)],
+                nvec![Integer::from(1), Integer::new(), Integer::from(1)],
+                nvec![Integer::from(1), Integer::from(1), Integer::from(2)],
+            ],
+        };
+        let gs = l.gso();
+        let t = Float::with_val(53, gs.len()).ln();
+        let k = KleinSampler::init(&gs, t);
+        let mut rng = rand::thread_rng();
+        assert_eq!(k.sample_batch(&l, 100, &mut rng).len(), 100);
+        for (v, z) in k.sample_batch_coef(&l, 100, &mut rng) {
+            assert_eq!(v.vec, (&z * &l).vec);
+        }
+    }
+
+    #[test]
+    fn test_constant_time_mp() {
+        let l = Lattice {
+            basis: vec![
+                nvec![Integer::from(1), Integer::from(-1), Integer::from(1)],
+                nvec![Integer::from(1), Integer::new(), Integer::from(1)],
+                nvec![Integer::from(1), Integer::from(1), Integer::from(2)],
+            ],
+        };
+        let gs = l.gso();
+        let t = Float::with_val(53, gs.len()).ln();
+        let k = KleinSampler::init(&gs, t).with_mode(SampleMode::ConstantTime);
+        let mut rng = rand::thread_rng();
+        for (v, z) in k.sample_batch_coef(&l, 100, &mut rng) {
+            assert_eq!(v.vec, (&z * &l).vec);
+        }
+    }
 }
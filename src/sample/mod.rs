@@ -1,2 +1,3 @@
+mod cdt;
 mod klein;
-pub use klein::{KleinSampler, Sample};
+pub use klein::{KleinSampler, Sample, SampleGram, SampleMode};
@@ -1,4 +1,6 @@
-use crate::{GaussReduce, KleinSampler, Lattice, Sample, Vector};
+use super::{Arith, Space, Stop};
+use crate::{Invariants, KleinSampler, Lattice, Overflow, Ring, Vector, GSO};
+use rand::RngCore;
 use rug::{Float, Integer};
 
 /**
@@ -39,6 +41,23 @@ let short_vecs = gs.sieve();
 // Short vectors sorted in ascending order
 assert_eq!(short_vecs[0].norm, b[0].norm);
 ```
+
+Machine integer sieves can detect overflow and fall back to arbitrary precision
+
+```rust
+use svp::*;
+
+let b: Vec<Vector<i64>> = vec![nvec![1 << 32, 0], nvec![0, 1]];
+let l = Lattice { basis: b.clone() };
+let mut gs = gsieve![l, (b.len() as f64).ln()];
+
+// Inner products of 2^32 entries do not fit in an i64
+assert_eq!(gs.try_sieve().unwrap_err(), Overflow);
+
+// Restarts with rug::Integer entries
+let short_vecs = gs.sieve_or_promote();
+assert_eq!(short_vecs[0].norm.as_ref().unwrap(), &1);
+```
 **/
 
 #[derive(Debug)]
@@ -50,83 +69,116 @@ pub struct GaussSieve<T, U> {
     pub s: Vec<Vector<T>>,
 }
 
-/// Mutually reduce sample list with respect to v
-trait ListReduce<T> {
-    /// After Gauss reduction, the angle between any
-    /// two vectors in the list is at least 60 degrees
-    fn reduce(&mut self, v: &mut Vector<T>);
-}
-
 /// Main `Sieve` loop
 pub trait Sieve<T> {
     /// Returns a list of short vectors sorted in ascending order
+    ///
+    /// Panics if a machine integer overflows, see `try_sieve`.
     fn sieve(&mut self) -> Vec<Vector<T>>;
+
+    /// Like `sieve`, but stops with an error if a machine integer overflows
+    ///
+    /// On error the list and the stack of the sieve are left as they were
+    /// before the call.
+    fn try_sieve(&mut self) -> Result<Vec<Vector<T>>, Overflow>;
+
+    /// Like `sieve`, drawing samples from `rng` and stopping as given by `stop`
+    fn sieve_with(&mut self, rng: &mut dyn RngCore, stop: Stop) -> Vec<Vector<T>>;
+
+    /// Like `try_sieve`, drawing samples from `rng` and stopping as given by `stop`
+    fn try_sieve_with(
+        &mut self,
+        rng: &mut dyn RngCore,
+        stop: Stop,
+    ) -> Result<Vec<Vector<T>>, Overflow>;
 }
 
-macro_rules! lr_impl {
-    ($t:ty, $u:ty) => {
-        impl ListReduce<$t> for GaussSieve<$t, $u> {
-            fn reduce(&mut self, v: &mut Vector<$t>) {
-                let mut index = 0;
-                let mut reduced = true;
-                while reduced {
-                    reduced = false;
-                    for i in 0..self.l.len() {
-                        if self.l[i].norm > v.norm {
-                            index = i;
-                            break;
-                        }
-                        if v.reduce(&self.l[i]) {
-                            reduced = true;
-                        }
-                    }
-                }
+impl<T: Ring> Space<T> for GaussSieve<T, T::Field> {
+    fn reduce_pair<A: Arith>(&self, u: &mut Vector<T>, v: &Vector<T>) -> Result<bool, Overflow> {
+        A::reduce(u, v)
+    }
 
-                if v.norm.as_ref().unwrap() != &0 {
-                    self.l.insert(index, v.clone());
-                    index += 1;
-                    while index < self.l.len() {
-                        if self.l[index].reduce(&v) {
-                            self.s.push(self.l[index].clone());
-                            self.l.remove(index);
-                        } else {
-                            index += 1;
-                        }
-                    }
-                }
-            }
-        }
-    };
+    fn norm<A: Arith>(&self, v: &Vector<T>) -> Result<T, Overflow> {
+        T::checked_dot(&v.vec, &v.vec)
+    }
+
+    fn sample_batch<A: Arith>(
+        &self,
+        n: usize,
+        rng: &mut dyn RngCore,
+    ) -> Result<Vec<Vector<T>>, Overflow> {
+        A::sample(&self.k, &self.b, n, rng)
+    }
+
+    fn lists(&mut self) -> (&mut Vec<Vector<T>>, &mut Vec<Vector<T>>) {
+        (&mut self.l, &mut self.s)
+    }
 }
 
-macro_rules! sieve_impl {
-    ($t:ty, $u:ty) => {
-        impl Sieve<$t> for GaussSieve<$t, $u> {
-            fn sieve(&mut self) -> Vec<Vector<$t>> {
-                let mut c = 0.0;
-                let mut ml = self.l.len() as f64;
-                let mut min_norm = self.b.basis[0].norm.clone();
-                while c < ml * 0.1 + 200.0 {
-                    let mut v: Vector<$t> = match self.s.is_empty() {
-                        false => self.s.pop().unwrap(),
-                        true => self.k.sample(&self.b),
-                    };
-                    self.reduce(&mut v);
-                    if v.norm.as_ref().unwrap() == &0 {
-                        c += 1.0;
-                    } else if v.norm < min_norm {
-                        min_norm = v.norm;
-                    }
-                    if self.l.len() as f64 > ml {
-                        ml = self.l.len() as f64;
-                    }
+impl<T: Ring> Sieve<T> for GaussSieve<T, T::Field> {
+    fn sieve(&mut self) -> Vec<Vector<T>> {
+        self.sieve_with(&mut rand::thread_rng(), Stop::Heuristic)
+    }
+
+    fn try_sieve(&mut self) -> Result<Vec<Vector<T>>, Overflow> {
+        self.try_sieve_with(&mut rand::thread_rng(), Stop::Heuristic)
+    }
+
+    fn sieve_with(&mut self, rng: &mut dyn RngCore, stop: Stop) -> Vec<Vector<T>> {
+        self.try_sieve_with(rng, stop)
+            .expect("machine integer overflow, see try_sieve and sieve_or_promote")
+    }
+
+    fn try_sieve_with(
+        &mut self,
+        rng: &mut dyn RngCore,
+        stop: Stop,
+    ) -> Result<Vec<Vector<T>>, Overflow> {
+        super::try_sieve_with(self, rng, stop)
+    }
+}
+
+impl<T> GaussSieve<T, T::Field>
+where
+    T: Ring,
+    Integer: From<T>,
+{
+    /// The ratio ‖v‖ / GH(L(b)) of an output vector to the gaussian heuristic
+    pub fn gh_ratio(&self, v: &Vector<T>) -> f64 {
+        self.b.gh_ratio(v)
+    }
+}
+
+impl<T> GaussSieve<T, f64>
+where
+    T: Ring<Field = f64>,
+    Integer: From<T>,
+{
+    /// Run the sieve, restarting with the `Integer` backend if a machine integer overflows
+    pub fn sieve_or_promote(&mut self) -> Vec<Vector<Integer>> {
+        self.sieve_or_promote_with(&mut rand::thread_rng(), Stop::Heuristic)
+    }
+
+    /// Like `sieve_or_promote`, drawing samples from `rng` and stopping as
+    /// given by `stop`
+    pub fn sieve_or_promote_with(
+        &mut self,
+        rng: &mut dyn RngCore,
+        stop: Stop,
+    ) -> Vec<Vector<Integer>> {
+        match self.try_sieve_with(rng, stop) {
+            Ok(res) => res.iter().map(Vector::from).collect(),
+            Err(Overflow) => {
+                let mut b = Lattice::<Integer>::from(&self.b);
+                for v in b.basis.iter_mut() {
+                    v.norm = Some(&*v * &*v);
                 }
-                let mut res: Vec<Vector<$t>> = self.l.iter().map(|i| &self.b * i).collect();
-                res.sort_by(|a, b| a.norm.partial_cmp(&b.norm).unwrap());
-                res
+                let t = Float::with_val(53, *self.k.t());
+                let mut gs = crate::gsieve![b, t];
+                gs.sieve_with(rng, stop)
             }
         }
-    };
+    }
 }
 
 /**
@@ -138,7 +190,7 @@ Initializes the Gauss Sieve
 use svp::*;
 
 // 3x3 Identity matrix
-let mut b = vec![nvec![1, 0, 0], nvec![0, 1, 0], nvec![0, 0, 1]];
+let mut b = vec![nvec![1i64, 0, 0], nvec![0, 1, 0], nvec![0, 0, 1]];
 
 // Compute norms
 for i in 0..b.len() {
@@ -161,7 +213,6 @@ let short_vecs = gs.sieve();
 assert_eq!(short_vecs[0].norm, b[0].norm);
 ```
 **/
-
 #[macro_export]
 macro_rules! gsieve {
     ($l:expr,$t:expr) => {{
@@ -174,20 +225,17 @@ macro_rules! gsieve {
     }};
 }
 
-/* Sieving type definitions */
-lr_impl!(i64, f64);
-sieve_impl!(i64, f64);
-lr_impl!(Integer, Float);
-sieve_impl!(Integer, Float);
-
 #[cfg(test)]
 mod tests {
+    use crate::testing;
     use crate::*;
+    use rand::SeedableRng;
+    use rand_chacha::ChaCha20Rng;
     use rug::{Float, Integer};
 
     #[test]
     fn test_identity() {
-        let mut b = vec![nvec![1, 0, 0], nvec![0, 1, 0], nvec![0, 0, 1]];
+        let mut b = vec![nvec![1i64, 0, 0], nvec![0, 1, 0], nvec![0, 0, 1]];
         for i in 0..b.len() {
             b[i].norm = Some(&b[i] * &b[i]);
         }
@@ -203,19 +251,44 @@ mod tests {
 
     #[test]
     fn test_dim10() {
-        let mut b = vec![
-            nvec![-1, 0, 1, 0, 1, 0, 0, 0, -1, 1],
-            nvec![-2, 2, -1, 0, 2, 3, 0, 1, 0, -2],
-            nvec![-3, 1, -1, 1, 0, -4, -1, -2, 0, 0],
-            nvec![1, 6, 0, 0, 1, 0, 2, 0, 0, 2],
-            nvec![-2, 1, -4, -1, -1, 0, 0, 4, -3, 2],
-            nvec![1, 0, -5, -10, 4, -3, -2, 0, 3, 4],
-            nvec![5, 0, -4, 4, 6, -6, 0, 4, -9, -7],
-            nvec![4, 3, -2, -7, -2, 3, 0, -6, -12, -2],
-            nvec![1, 6, 0, 1, -3, 3, -15, 3, -1, 2],
-            nvec![0, 3, 11, -9, -5, -4, -3, 8, -1, -7],
-        ];
+        let mut rng = ChaCha20Rng::seed_from_u64(10);
+        let q = Integer::from(31);
+        let r = Lattice::random_q_ary(5, 10, &q, &mut rng).random_unimodular(&mut rng);
+        let mut l: Lattice<i64> = r.narrow().unwrap();
+        l.lll(0.99);
+        let t = (l.basis.len() as f64).ln();
+        let norm = l.shortest().norm;
+        assert_eq!(norm, Some(30));
+
+        let mut gs = gsieve![l, t];
+
+        let short_vecs = gs.sieve_with(&mut rng, Stop::Heuristic);
+        assert_eq!(short_vecs[0].norm, norm);
+        assert!(gs.gh_ratio(&short_vecs[0]) > 0.0);
+    }
+
+    #[test]
+    #[cfg(feature = "int-backends")]
+    fn test_dim10_i128() {
+        let mut rng = ChaCha20Rng::seed_from_u64(10);
+        let q = Integer::from(31);
+        let r = Lattice::random_q_ary(5, 10, &q, &mut rng).random_unimodular(&mut rng);
+        let mut l: Lattice<i128> = r.narrow().unwrap();
+        l.lll(0.99);
+        let t = (l.basis.len() as f64).ln();
+        let norm = l.shortest().norm;
+        assert_eq!(norm, Some(30));
 
+        let mut gs = gsieve![l, t];
+
+        let short_vecs = gs.sieve_with(&mut rng, Stop::Heuristic);
+        assert_eq!(short_vecs[0].norm, norm);
+    }
+
+    #[test]
+    #[cfg(feature = "int-backends")]
+    fn test_identity_i32() {
+        let mut b: Vec<Vector<i32>> = vec![nvec![1, 0, 0], nvec![0, 1, 0], nvec![0, 0, 1]];
         for i in 0..b.len() {
             b[i].norm = Some(&b[i] * &b[i]);
         }
@@ -226,7 +299,83 @@ mod tests {
         let mut gs = gsieve![l, t];
 
         let short_vecs = gs.sieve();
-        assert_eq!(short_vecs[0].norm.unwrap(), 62);
+        assert_eq!(short_vecs[0].norm, b[0].norm);
+    }
+
+    #[test]
+    fn test_overflow() {
+        let b: Vec<Vector<i64>> = vec![nvec![1 << 32, 0, 0], nvec![0, 1 << 32, 0], nvec![0, 0, 1]];
+
+        let l = Lattice { basis: b.clone() };
+        let t = (b.len() as f64).ln();
+
+        let mut gs = gsieve![l, t];
+        assert_eq!(gs.try_sieve().unwrap_err(), Overflow);
+
+        let short_vecs = gs.sieve_or_promote();
+        assert_eq!(short_vecs[0].norm.as_ref().unwrap(), &1);
+
+        // (0, 1) enters the list before (2^32, 0) overflows, and is removed again
+        let l = Lattice {
+            basis: vec![nvec![1i64 << 32, 0], nvec![0, 1]],
+        };
+        let mut gs = gsieve![l, 2f64.ln()];
+        assert_eq!(gs.try_sieve().unwrap_err(), Overflow);
+        assert!(gs.l.is_empty());
+        assert_eq!(gs.s.len(), 2);
+    }
+
+    #[test]
+    #[should_panic(expected = "machine integer overflow")]
+    fn test_overflow_panics() {
+        let b: Vec<Vector<i64>> = vec![nvec![1 << 32, 0], nvec![0, 1]];
+        let l = Lattice { basis: b };
+        let mut gs = gsieve![l, 2f64.ln()];
+        gs.sieve();
+    }
+
+    #[test]
+    fn test_rectangular() {
+        // A rank 3 generating set in Z^5, reduced to a 3x5 basis
+        let mut l: Lattice<i64> = Lattice {
+            basis: vec![
+                nvec![2, 0, 1, 4, 3],
+                nvec![1, 1, 0, 2, 1],
+                nvec![3, 1, 1, 6, 4],
+                nvec![0, 5, -1, 1, 2],
+            ],
+        };
+        assert_eq!(l.lll(0.99), 3);
+        let norm = l.shortest().norm;
+        let t = (l.basis.len() as f64).ln();
+
+        let mut gs = gsieve![l, t];
+        let short_vecs = gs.try_sieve().unwrap();
+        assert_eq!(short_vecs[0].norm, norm);
+        assert_eq!(short_vecs[0].vec.len(), 5);
+    }
+
+    #[test]
+    fn test_seeded() {
+        let mut l = testing::dim10();
+        for v in l.basis.iter_mut() {
+            v.norm = Some(&*v * &*v);
+        }
+        let t = (l.basis.len() as f64).ln();
+
+        // The same seed gives the same list
+        let run = |seed: u64, stop: Stop| {
+            let mut gs = gsieve![l.clone(), t];
+            gs.sieve_with(&mut ChaCha20Rng::seed_from_u64(seed), stop)
+        };
+        let (a, b) = (run(7, Stop::Heuristic), run(7, Stop::Heuristic));
+        assert_eq!(a.len(), b.len());
+        assert!(a.iter().zip(&b).all(|(u, v)| u.vec == v.vec));
+        assert_eq!(a[0].norm, Some(5));
+
+        // Stop as soon as the bound is met, or after some collisions
+        assert!(run(7, Stop::Norm(100.0))[0].norm <= Some(100));
+        assert!(!run(7, Stop::Collisions(1)).is_empty());
     }
 
     #[test]
@@ -251,139 +400,15 @@ mod tests {
 
     #[test]
     fn test_dim10_mp() {
-        let mut b = vec![
-            nvec![
-                Integer::from(3),
-                Integer::from(-4),
-                Integer::from(-1),
-                Integer::from(-2),
-                Integer::new(),
-                Integer::from(-2),
-                Integer::from(-1),
-                Integer::from(1),
-                Integer::from(-1),
-                Integer::new()
-            ],
-            nvec![
-                Integer::from(-1),
-                Integer::from(1),
-                Integer::from(-6),
-                Integer::from(1),
-                Integer::from(-1),
-                Integer::from(-2),
-                Integer::from(-3),
-                Integer::from(-1),
-                Integer::from(3),
-                Integer::new()
-            ],
-            nvec![
-                Integer::from(1),
-                Integer::new(),
-                Integer::from(-7),
-                Integer::from(3),
-                Integer::new(),
-                Integer::new(),
-                Integer::from(1),
-                Integer::from(1),
-                Integer::from(-3),
-                Integer::from(-1)
-            ],
-            nvec![
-                Integer::new(),
-                Integer::from(-2),
-                Integer::from(1),
-                Integer::from(5),
-                Integer::new(),
-                Integer::from(-3),
-                Integer::from(-6),
-                Integer::from(-3),
-                Integer::from(-4),
-                Integer::from(-1)
-            ],
-            nvec![
-                Integer::from(-8),
-                Integer::from(-1),
-                Integer::from(-1),
-                Integer::from(1),
-                Integer::new(),
-                Integer::from(-4),
-                Integer::from(-2),
-                Integer::from(1),
-                Integer::new(),
-                Integer::from(7)
-            ],
-            nvec![
-                Integer::from(-7),
-                Integer::from(-2),
-                Integer::from(-1),
-                Integer::from(-1),
-                Integer::from(1),
-                Integer::from(3),
-                Integer::from(1),
-                Integer::from(-1),
-                Integer::from(-1),
-                Integer::from(-9)
-            ],
-            nvec![
-                Integer::from(3),
-                Integer::from(5),
-                Integer::from(3),
-                Integer::from(5),
-                Integer::from(-6),
-                Integer::from(-11),
-                Integer::from(-1),
-                Integer::from(12),
-                Integer::from(-4),
-                Integer::from(-3)
-            ],
-            nvec![
-                Integer::from(-1),
-                Integer::from(9),
-                Integer::from(-3),
-                Integer::from(-13),
-                Integer::from(-7),
-                Integer::from(-3),
-                Integer::from(-8),
-                Integer::from(-6),
-                Integer::from(-12),
-                Integer::from(-2)
-            ],
-            nvec![
-                Integer::from(2),
-                Integer::from(-8),
-                Integer::new(),
-                Integer::from(12),
-                Integer::from(-27),
-                Integer::from(2),
-                Integer::from(9),
-                Integer::new(),
-                Integer::from(11),
-                Integer::from(1)
-            ],
-            nvec![
-                Integer::from(-4),
-                Integer::from(4),
-                Integer::from(2),
-                Integer::from(1),
-                Integer::from(-2),
-                Integer::from(-29),
-                Integer::from(22),
-                Integer::from(-17),
-                Integer::from(-1),
-                Integer::from(-6)
-            ],
-        ];
-
-        for i in 0..b.len() {
-            b[i].norm = Some(&b[i] * &b[i]);
-        }
-
-        let l = Lattice { basis: b.clone() };
-        let t = Float::with_val(32, b.len()).ln();
+        let mut rng = ChaCha20Rng::seed_from_u64(11);
+        let mut l = Lattice::knapsack(10, 12, &mut rng).random_unimodular(&mut rng);
+        l.lll(0.99);
+        let t = Float::with_val(32, l.basis.len()).ln();
+        let norm = l.shortest().norm;
 
         let mut gs = gsieve![l, t];
 
-        let short_vecs = gs.sieve();
-        assert_eq!(short_vecs[0].norm.as_ref().unwrap(), &111);
+        let short_vecs = gs.sieve_with(&mut rng, Stop::Heuristic);
+        assert_eq!(short_vecs[0].norm, norm);
     }
 }
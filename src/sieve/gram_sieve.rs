@@ -0,0 +1,182 @@
+use super::{Arith, Space, Stop};
+use crate::{GramLattice, Invariants, KleinSampler, Overflow, Ring, Sieve, Vector, GSO};
+use rand::RngCore;
+use rug::Integer;
+
+/**
+
+The Gauss Sieve in the coefficient space of a `GramLattice`
+
+List vectors are integer coefficient vectors, reduced with respect to the
+inner product given by the Gram matrix, so no explicit basis is needed.
+
+# Examples
+
+```rust
+use svp::*;
+
+// The hexagonal lattice A2 as a binary quadratic form
+let g = GramLattice::new(vec![nvec![2i64, -1], nvec![-1, 2]]);
+
+// Rejection sampling parameter
+let t = (g.dim() as f64).ln();
+
+// Init and run the sieve
+let mut gs = GramSieve::init(g, t);
+let short_vecs = gs.sieve();
+
+// Short coefficient vectors sorted in ascending order
+assert_eq!(short_vecs[0].norm, Some(2));
+
+// Compared to the gaussian heuristic
+let ratio = gs.gh_ratio(&short_vecs[0]);
+```
+**/
+
+#[derive(Debug)]
+pub struct GramSieve<T, U> {
+    pub g: GramLattice<T>,
+    pub k: KleinSampler<U>,
+    pub l: Vec<Vector<T>>,
+    pub s: Vec<Vector<T>>,
+}
+
+impl<T: Ring> GramSieve<T, T::Field> {
+    /// Initialize the sieve with the basis vectors in its stack
+    pub fn init(g: GramLattice<T>, t: T::Field) -> Self {
+        Self {
+            s: (0..g.dim()).map(|i| g.unit(i)).collect(),
+            k: KleinSampler::init(&g.gso(), t),
+            g,
+            l: vec![],
+        }
+    }
+}
+
+impl<T> GramSieve<T, T::Field>
+where
+    T: Ring,
+    Integer: From<T>,
+{
+    /// The ratio ‖v‖ / GH(L) of an output vector to the gaussian heuristic
+    pub fn gh_ratio(&self, v: &Vector<T>) -> f64 {
+        self.g.gh_ratio(v)
+    }
+}
+
+/// Gauss reduce u with respect to v in the metric of g
+fn gram_reduce<T: Ring, A: Arith>(
+    g: &GramLattice<T>,
+    u: &mut Vector<T>,
+    v: &Vector<T>,
+) -> Result<bool, Overflow> {
+    let norm = v.norm.as_ref().unwrap();
+    let ip = A::inner(g, u, v)?;
+    let ip_abs = ip.checked_abs()?;
+    if norm >= &ip_abs.checked_add(&ip_abs)? {
+        return Ok(false);
+    }
+
+    let q = ip.round_div(norm);
+    for (a, b) in u.vec.iter_mut().zip(&v.vec) {
+        A::sub_mul(a, &q, b)?;
+    }
+    u.norm = Some(A::inner(g, u, u)?);
+    Ok(true)
+}
+
+impl<T: Ring> Space<T> for GramSieve<T, T::Field> {
+    fn reduce_pair<A: Arith>(&self, u: &mut Vector<T>, v: &Vector<T>) -> Result<bool, Overflow> {
+        gram_reduce::<T, A>(&self.g, u, v)
+    }
+
+    fn norm<A: Arith>(&self, v: &Vector<T>) -> Result<T, Overflow> {
+        A::inner(&self.g, v, v)
+    }
+
+    fn sample_batch<A: Arith>(
+        &self,
+        n: usize,
+        rng: &mut dyn RngCore,
+    ) -> Result<Vec<Vector<T>>, Overflow> {
+        A::sample_gram(&self.k, &self.g, n, rng)
+    }
+
+    fn lists(&mut self) -> (&mut Vec<Vector<T>>, &mut Vec<Vector<T>>) {
+        (&mut self.l, &mut self.s)
+    }
+}
+
+impl<T: Ring> Sieve<T> for GramSieve<T, T::Field> {
+    fn sieve(&mut self) -> Vec<Vector<T>> {
+        self.sieve_with(&mut rand::thread_rng(), Stop::Heuristic)
+    }
+
+    fn try_sieve(&mut self) -> Result<Vec<Vector<T>>, Overflow> {
+        self.try_sieve_with(&mut rand::thread_rng(), Stop::Heuristic)
+    }
+
+    fn sieve_with(&mut self, rng: &mut dyn RngCore, stop: Stop) -> Vec<Vector<T>> {
+        self.try_sieve_with(rng, stop)
+            .expect("machine integer overflow, see try_sieve")
+    }
+
+    fn try_sieve_with(
+        &mut self,
+        rng: &mut dyn RngCore,
+        stop: Stop,
+    ) -> Result<Vec<Vector<T>>, Overflow> {
+        super::try_sieve_with(self, rng, stop)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::*;
+    use rand::SeedableRng;
+    use rand_chacha::ChaCha20Rng;
+    use rug::{Float, Integer};
+
+    #[test]
+    fn test_dim10() {
+        let mut rng = ChaCha20Rng::seed_from_u64(10);
+        let q = Integer::from(31);
+        let r = Lattice::random_q_ary(5, 10, &q, &mut rng).random_unimodular(&mut rng);
+        let mut l: Lattice<i64> = r.narrow().unwrap();
+        l.lll(0.99);
+        let g = l.gram();
+        let t = (g.dim() as f64).ln();
+
+        let mut gs = GramSieve::init(g, t);
+        let short_vecs = gs.try_sieve_with(&mut rng, Stop::Heuristic).unwrap();
+        assert_eq!(short_vecs[0].norm, l.shortest().norm);
+        assert_eq!((&short_vecs[0] * &l).norm, short_vecs[0].norm);
+        assert_eq!(gs.gh_ratio(&short_vecs[0]), l.gh_ratio(&l.shortest()));
+    }
+
+    #[test]
+    fn test_mp() {
+        let g = GramLattice::new(vec![
+            nvec![Integer::from(4), Integer::from(2), Integer::from(2)],
+            nvec![Integer::from(2), Integer::from(4), Integer::from(2)],
+            nvec![Integer::from(2), Integer::from(2), Integer::from(5)],
+        ]);
+        let t = Float::with_val(32, g.dim()).ln();
+
+        let mut gs = GramSieve::init(g.clone(), t);
+        let short_vecs = gs.sieve();
+        assert_eq!(short_vecs[0].norm, g.shortest().norm);
+    }
+
+    #[test]
+    fn test_overflow() {
+        // Every nonzero sample overflows, either its norm or its reduction
+        let g = GramLattice::new(vec![nvec![1i64 << 62, 0], nvec![0, 1 << 62]]);
+        let mut gs = GramSieve::init(g, 2f64.ln());
+        let mut rng = ChaCha20Rng::seed_from_u64(1);
+        let res = gs.try_sieve_with(&mut rng, Stop::Heuristic);
+        assert_eq!(res.unwrap_err(), Overflow);
+        assert!(gs.l.is_empty());
+        assert_eq!(gs.s.len(), 2);
+    }
+}
@@ -1,4 +1,269 @@
 mod gauss_sieve;
+mod gram_sieve;
 
+use crate::{
+    Field, GaussReduce, GramLattice, KleinSampler, Lattice, Overflow, Ring, Sample, SampleGram,
+    Vector,
+};
+use rand::RngCore;
+
+#[allow(unused_imports)]
 pub use crate::gsieve;
 pub use gauss_sieve::{GaussSieve, Sieve};
+pub use gram_sieve::GramSieve;
+
+/// When a sieve stops
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub enum Stop {
+    /// After 0.1·l + 200 collisions, l being the largest list size so far \[MV10\]
+    #[default]
+    Heuristic,
+    /// After the given number of collisions
+    Collisions(usize),
+    /// Once a vector of squared norm at most the bound is found, or as
+    /// `Heuristic` if there is none
+    Norm(f64),
+}
+
+impl Stop {
+    /// Decide whether to stop after `c` collisions, given the largest list
+    /// size `ml` and the least squared norm found so far
+    pub(crate) fn done(&self, c: usize, ml: usize, min_norm: f64) -> bool {
+        match *self {
+            Stop::Collisions(n) => c >= n,
+            Stop::Norm(bound) if min_norm <= bound => true,
+            _ => c as f64 >= ml as f64 * 0.1 + 200.0,
+        }
+    }
+}
+
+/// Number of lattice points drawn from the sampler at once
+const SAMPLE_BATCH: usize = 64;
+
+/// The operations in which the Gauss sieve and the Gram sieve differ, so
+/// that list reduction, stopping and rollback are written once
+pub(crate) trait Space<T: Ring> {
+    /// Gauss reduce u with respect to v, returning whether u changed
+    fn reduce_pair<A: Arith>(&self, u: &mut Vector<T>, v: &Vector<T>) -> Result<bool, Overflow>;
+
+    /// Squared norm of a stack vector that has none yet
+    fn norm<A: Arith>(&self, v: &Vector<T>) -> Result<T, Overflow>;
+
+    /// Draw n lattice points along with their squared norms
+    fn sample_batch<A: Arith>(
+        &self,
+        n: usize,
+        rng: &mut dyn RngCore,
+    ) -> Result<Vec<Vector<T>>, Overflow>;
+
+    /// The list and the stack of the sieve
+    fn lists(&mut self) -> (&mut Vec<Vector<T>>, &mut Vec<Vector<T>>);
+}
+
+/// Mutually reduce the list l with respect to v, pushing the list vectors
+/// reduced by v onto the stack s
+///
+/// After Gauss reduction, the angle between any two vectors in the list is
+/// at least 60 degrees.
+fn list_reduce<T: Ring, S: Space<T>, A: Arith>(
+    sp: &S,
+    l: &mut Vec<Vector<T>>,
+    s: &mut Vec<Vector<T>>,
+    v: &mut Vector<T>,
+) -> Result<(), Overflow> {
+    let mut index = 0;
+    let mut reduced = true;
+    while reduced {
+        reduced = false;
+        for i in 0..l.len() {
+            if l[i].norm > v.norm {
+                index = i;
+                break;
+            }
+            if sp.reduce_pair::<A>(v, &l[i])? {
+                reduced = true;
+            }
+        }
+    }
+
+    if !v.norm.as_ref().unwrap().is_zero() {
+        l.insert(index, v.clone());
+        index += 1;
+        while index < l.len() {
+            if sp.reduce_pair::<A>(&mut l[index], v)? {
+                s.push(l.remove(index));
+            } else {
+                index += 1;
+            }
+        }
+    }
+    Ok(())
+}
+
+/// Run a sieve on the list l and the stack s with the arithmetic `A`
+fn run<T: Ring, S: Space<T>, A: Arith>(
+    sp: &S,
+    l: &mut Vec<Vector<T>>,
+    s: &mut Vec<Vector<T>>,
+    rng: &mut dyn RngCore,
+    stop: Stop,
+) -> Result<Vec<Vector<T>>, Overflow> {
+    let mut c = 0;
+    let mut ml = l.len();
+    let mut min_norm = f64::INFINITY;
+    let mut samples: Vec<Vector<T>> = vec![];
+    while !stop.done(c, ml, min_norm) {
+        let mut v: Vector<T> = match s.pop() {
+            Some(v) => v,
+            None => {
+                if samples.is_empty() {
+                    samples = sp.sample_batch::<A>(SAMPLE_BATCH, rng)?;
+                }
+                samples.pop().unwrap()
+            }
+        };
+        if v.norm.is_none() {
+            v.norm = Some(sp.norm::<A>(&v)?);
+        }
+        list_reduce::<T, S, A>(sp, l, s, &mut v)?;
+        let norm = v.norm.as_ref().unwrap();
+        if norm.is_zero() {
+            c += 1;
+        } else if let Stop::Norm(_) = stop {
+            min_norm = min_norm.min(norm.to_field(T::PRECISION).to_f64());
+        }
+        ml = ml.max(l.len());
+    }
+    let mut res = l.clone();
+    res.sort_by(|a, b| a.norm.partial_cmp(&b.norm).unwrap());
+    Ok(res)
+}
+
+/// Run a sieve, leaving its list and stack as they were if a machine
+/// integer overflows
+pub(crate) fn try_sieve_with<T: Ring, S: Space<T>>(
+    sp: &mut S,
+    rng: &mut dyn RngCore,
+    stop: Stop,
+) -> Result<Vec<Vector<T>>, Overflow> {
+    let (l, s) = sp.lists();
+    let (mut l, mut s) = (std::mem::take(l), std::mem::take(s));
+    let res = match T::EXACT {
+        true => run::<T, S, Exact>(sp, &mut l, &mut s, rng, stop),
+        false => {
+            let (mut l2, mut s2) = (l.clone(), s.clone());
+            let res = run::<T, S, Checked>(sp, &mut l2, &mut s2, rng, stop);
+            if res.is_ok() {
+                (l, s) = (l2, s2);
+            }
+            res
+        }
+    };
+    let (sl, ss) = sp.lists();
+    (*sl, *ss) = (l, s);
+    res
+}
+
+/// Arithmetic of the sieve inner loops, chosen at compile time
+pub(crate) trait Arith {
+    /// Gauss reduce u with respect to v
+    fn reduce<T: Ring>(u: &mut Vector<T>, v: &Vector<T>) -> Result<bool, Overflow>;
+
+    /// Inner product uᵀ·G·v
+    fn inner<T: Ring>(g: &GramLattice<T>, u: &Vector<T>, v: &Vector<T>) -> Result<T, Overflow>;
+
+    /// Subtract q·b from a
+    fn sub_mul<T: Ring>(a: &mut T, q: &T, b: &T) -> Result<(), Overflow>;
+
+    fn sample<T: Ring>(
+        k: &KleinSampler<T::Field>,
+        b: &Lattice<T>,
+        n: usize,
+        rng: &mut dyn RngCore,
+    ) -> Result<Vec<Vector<T>>, Overflow>;
+
+    fn sample_gram<T: Ring>(
+        k: &KleinSampler<T::Field>,
+        g: &GramLattice<T>,
+        n: usize,
+        rng: &mut dyn RngCore,
+    ) -> Result<Vec<Vector<T>>, Overflow>;
+}
+
+/// Plain arithmetic, for backends with `Ring::EXACT`
+pub(crate) struct Exact;
+
+/// Arithmetic that reports machine integer overflow
+pub(crate) struct Checked;
+
+impl Arith for Exact {
+    #[inline]
+    fn reduce<T: Ring>(u: &mut Vector<T>, v: &Vector<T>) -> Result<bool, Overflow> {
+        Ok(u.reduce(v))
+    }
+
+    #[inline]
+    fn inner<T: Ring>(g: &GramLattice<T>, u: &Vector<T>, v: &Vector<T>) -> Result<T, Overflow> {
+        Ok(g.inner(u, v))
+    }
+
+    #[inline]
+    fn sub_mul<T: Ring>(a: &mut T, q: &T, b: &T) -> Result<(), Overflow> {
+        a.sub_mul(q, b);
+        Ok(())
+    }
+
+    fn sample<T: Ring>(
+        k: &KleinSampler<T::Field>,
+        b: &Lattice<T>,
+        n: usize,
+        rng: &mut dyn RngCore,
+    ) -> Result<Vec<Vector<T>>, Overflow> {
+        Ok(k.sample_batch(b, n, rng))
+    }
+
+    fn sample_gram<T: Ring>(
+        k: &KleinSampler<T::Field>,
+        g: &GramLattice<T>,
+        n: usize,
+        rng: &mut dyn RngCore,
+    ) -> Result<Vec<Vector<T>>, Overflow> {
+        Ok(k.sample_gram_batch(g, n, rng))
+    }
+}
+
+impl Arith for Checked {
+    #[inline]
+    fn reduce<T: Ring>(u: &mut Vector<T>, v: &Vector<T>) -> Result<bool, Overflow> {
+        u.try_reduce(v)
+    }
+
+    #[inline]
+    fn inner<T: Ring>(g: &GramLattice<T>, u: &Vector<T>, v: &Vector<T>) -> Result<T, Overflow> {
+        g.checked_inner(u, v)
+    }
+
+    #[inline]
+    fn sub_mul<T: Ring>(a: &mut T, q: &T, b: &T) -> Result<(), Overflow> {
+        *a = a.checked_sub(&q.checked_mul(b)?)?;
+        Ok(())
+    }
+
+    fn sample<T: Ring>(
+        k: &KleinSampler<T::Field>,
+        b: &Lattice<T>,
+        n: usize,
+        rng: &mut dyn RngCore,
+    ) -> Result<Vec<Vector<T>>, Overflow> {
+        k.try_sample_batch(b, n, rng)
+    }
+
+    fn sample_gram<T: Ring>(
+        k: &KleinSampler<T::Field>,
+        g: &GramLattice<T>,
+        n: usize,
+        rng: &mut dyn RngCore,
+    ) -> Result<Vec<Vector<T>>, Overflow> {
+        k.try_sample_gram_batch(g, n, rng)
+    }
+}
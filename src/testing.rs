@@ -0,0 +1,80 @@
+use crate::{nvec, Invariants, Lattice, Ring, Vector};
+use rug::Integer;
+
+/// A 10-dimensional basis with λ₁(L)² = 5
+pub(crate) fn dim10() -> Lattice<i64> {
+    Lattice {
+        basis: vec![
+            nvec![-1, 0, 1, 0, 1, 0, 0, 0, -1, 1],
+            nvec![-2, 2, -1, 0, 2, 3, 0, 1, 0, -2],
+            nvec![-3, 1, -1, 1, 0, -4, -1, -2, 0, 0],
+            nvec![1, 6, 0, 0, 1, 0, 2, 0, 0, 2],
+            nvec![-2, 1, -4, -1, -1, 0, 0, 4, -3, 2],
+            nvec![1, 0, -5, -10, 4, -3, -2, 0, 3, 4],
+            nvec![5, 0, -4, 4, 6, -6, 0, 4, -9, -7],
+            nvec![4, 3, -2, -7, -2, 3, 0, -6, -12, -2],
+            nvec![1, 6, 0, 1, -3, 3, -15, 3, -1, 2],
+            nvec![0, 3, 11, -9, -5, -4, -3, 8, -1, -7],
+        ],
+    }
+}
+
+/// A 4-dimensional knapsack-like basis with λ₁(L)² = 39
+pub(crate) fn knapsack4() -> Lattice<Integer> {
+    let l = Lattice {
+        basis: vec![
+            nvec![1i64, 0, 0, 1345],
+            nvec![0, 1, 0, 35],
+            nvec![0, 0, 1, 154],
+            nvec![0, 0, 0, 2048],
+        ],
+    };
+    Lattice::from(&l)
+}
+
+/// Three nearly parallel vectors with 200-bit entries, of rank 3
+pub(crate) fn parallel_mp() -> Lattice<Integer> {
+    let big: Integer = Integer::from(1) << 200;
+    Lattice {
+        basis: vec![
+            nvec![big.clone() + 1, big.clone(), Integer::from(3)],
+            nvec![big.clone(), big.clone() - 1, Integer::from(5)],
+            nvec![
+                Integer::from(2) * &big + 1,
+                Integer::from(2) * &big - 1,
+                Integer::from(9)
+            ],
+        ],
+    }
+}
+
+/// Five generators of a rank 3 lattice, including a zero vector
+pub(crate) fn generating_set() -> Lattice<i64> {
+    Lattice {
+        basis: vec![
+            nvec![2, 0, 1, 4, 3],
+            nvec![1, 1, 0, 2, 1],
+            nvec![3, 1, 1, 6, 4],
+            nvec![0, 5, -1, 1, 2],
+            nvec![0, 0, 0, 0, 0],
+        ],
+    }
+}
+
+/// Check that U is unimodular with B' = U·B, followed by the relations
+/// between the generators of B
+pub(crate) fn assert_transform<T>(b: &Lattice<T>, r: &Lattice<T>, u: &[Vector<T>])
+where
+    T: Ring,
+    Integer: From<T>,
+{
+    assert_eq!(u.len(), b.basis.len());
+    assert_eq!(Lattice { basis: u.to_vec() }.gram_det(), 1);
+    for (i, ui) in u.iter().enumerate() {
+        let v = ui * b;
+        match r.basis.get(i) {
+            Some(w) => assert_eq!(v.vec, w.vec),
+            None => assert!(v.vec.iter().all(|x| *x == T::zero())),
+        }
+    }
+}